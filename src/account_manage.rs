@@ -0,0 +1,19 @@
+use clap::Parser;
+
+pub mod decode;
+pub mod show;
+
+#[derive(Parser, Debug)]
+pub enum AccountManage {
+    /// show the owner, balance and decoded state of any account
+    Show(show::ShowArgs),
+    /// decode the data of an Anchor account into json
+    Decode(decode::DecodeArgs),
+}
+
+pub async fn handle_account_manage(account_manage: &AccountManage) -> anyhow::Result<()> {
+    match account_manage {
+        AccountManage::Show(args) => show::handle_show(args).await,
+        AccountManage::Decode(args) => decode::handle_decode(args).await,
+    }
+}
@@ -0,0 +1,32 @@
+use crate::config::get_rpc_client;
+use crate::output::print_json;
+use crate::program_manage::idl::{decode_account, load_idl};
+use clap::Parser;
+use solana_sdk::pubkey::Pubkey;
+
+/// decode the data of an Anchor account into json
+#[derive(Parser, Debug)]
+pub struct DecodeArgs {
+    /// Account address
+    pub address: String,
+
+    /// IDL file (optional, fetched from chain for the owner program if not provided)
+    #[clap(long)]
+    pub idl: Option<String>,
+}
+
+pub async fn handle_decode(args: &DecodeArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let address: Pubkey = args.address.parse()?;
+    let account = client.get_account(&address).await?;
+    let idl = load_idl(&client, args.idl.as_deref(), Some(&account.owner)).await?;
+
+    let (account_type, data) = decode_account(&idl, &account.data)?;
+    print_json(&serde_json::json!({
+        "address": address.to_string(),
+        "owner": account.owner.to_string(),
+        "lamports": account.lamports,
+        "type": account_type.name,
+        "data": data,
+    }))
+}
@@ -0,0 +1,209 @@
+use crate::config::get_rpc_client;
+use crate::output::print_json;
+use base64::Engine;
+use clap::{Parser, ValueEnum};
+use console::style;
+use solana_account_decoder_client_types::{UiAccount, UiAccountData, UiAccountEncoding};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::Response;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+
+/// show the owner, balance and decoded state of any account
+#[derive(Parser, Debug)]
+pub struct ShowArgs {
+    /// Account address
+    pub address: String,
+
+    /// Also print the raw data, or everything as json
+    #[clap(short, long, value_enum)]
+    pub output: Option<DataFormat>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataFormat {
+    /// Raw data as hex, 32 bytes per line
+    Hex,
+    /// Raw data as base64
+    Base64,
+    /// The account info, decoded state and base64 data as json
+    Json,
+}
+
+/// the account with `encoding`, read no earlier than `min_context_slot`
+async fn get_account_info(
+    client: &RpcClient,
+    address: &Pubkey,
+    encoding: UiAccountEncoding,
+    min_context_slot: Option<u64>,
+) -> anyhow::Result<Response<Option<UiAccount>>> {
+    // the typed client call decodes the data back to bytes, the parsed json
+    // is only kept in the raw response
+    Ok(client
+        .send(
+            RpcRequest::GetAccountInfo,
+            serde_json::json!([
+                address.to_string(),
+                RpcAccountInfoConfig {
+                    encoding: Some(encoding),
+                    commitment: Some(client.commitment()),
+                    min_context_slot,
+                    ..Default::default()
+                }
+            ]),
+        )
+        .await?)
+}
+
+pub async fn handle_show(args: &ShowArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let address: Pubkey = args.address.parse()?;
+
+    // the node decodes the state of known owners: system nonce, token account
+    // or mint, stake, vote, lookup table, upgradeable loader and sysvars.
+    // Anything else comes back as base64.
+    let response = get_account_info(&client, &address, UiAccountEncoding::JsonParsed, None).await?;
+    let slot = response.context.slot;
+    let account = response
+        .value
+        .ok_or_else(|| anyhow::anyhow!("Account {} not found", address))?;
+    let owner: Pubkey = account.owner.parse()?;
+    let (parsed, data) = match account.data {
+        UiAccountData::Json(parsed) => (Some(parsed), None),
+        data => (None, data.decode()),
+    };
+    let data_len = match (&parsed, &data) {
+        (Some(parsed), _) => parsed.space as usize,
+        (None, Some(data)) => data.len(),
+        (None, None) => account.space.unwrap_or_default() as usize,
+    };
+
+    // the raw bytes of a decoded account need a second read, at the same slot or later
+    let data = match (data, args.output) {
+        (Some(data), _) => data,
+        (None, Some(_)) => {
+            get_account_info(&client, &address, UiAccountEncoding::Base64, Some(slot))
+                .await?
+                .value
+                .and_then(|account| account.data.decode())
+                .ok_or_else(|| anyhow::anyhow!("Failed to read the data of {}", address))?
+        }
+        (None, None) => vec![],
+    };
+
+    let rent_exempt_minimum = client
+        .get_minimum_balance_for_rent_exemption(data_len)
+        .await?;
+    let rent_exempt = account.lamports >= rent_exempt_minimum;
+
+    if args.output == Some(DataFormat::Json) {
+        return print_json(&serde_json::json!({
+            "address": address.to_string(),
+            "owner": owner.to_string(),
+            "lamports": account.lamports,
+            "dataLength": data_len,
+            "executable": account.executable,
+            "rentEpoch": account.rent_epoch,
+            "rentExempt": rent_exempt,
+            "type": parsed.as_ref().map(|parsed| &parsed.program),
+            "parsed": parsed.as_ref().map(|parsed| &parsed.parsed),
+            "data": base64::engine::general_purpose::STANDARD.encode(&data),
+        }));
+    }
+
+    println!(
+        "{} {}",
+        style("Address:").bold().cyan(),
+        style(address).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Owner:").bold().cyan(),
+        style(owner).yellow()
+    );
+    println!(
+        "{} {} SOL",
+        style("Balance:").bold().cyan(),
+        style(lamports_to_sol(account.lamports)).green()
+    );
+    println!(
+        "{} {}",
+        style("Data length:").bold().cyan(),
+        style(data_len).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Executable:").bold().cyan(),
+        style(account.executable).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Rent epoch:").bold().cyan(),
+        style(account.rent_epoch).yellow()
+    );
+    println!(
+        "{} {} (minimum {} SOL)",
+        style("Rent exempt:").bold().cyan(),
+        if rent_exempt {
+            style("yes").green()
+        } else {
+            style("no").red()
+        },
+        lamports_to_sol(rent_exempt_minimum)
+    );
+
+    match &parsed {
+        Some(parsed) => {
+            println!(
+                "{} {}",
+                style("Type:").bold().cyan(),
+                style(&parsed.program).yellow()
+            );
+            for line in serde_json::to_string_pretty(&parsed.parsed)?.lines() {
+                println!("  {}", line);
+            }
+        }
+        None if data_len == 0 => {
+            println!(
+                "{} {}",
+                style("Type:").bold().cyan(),
+                style("no data").dim()
+            );
+        }
+        None => {
+            println!(
+                "{} {}",
+                style("Type:").bold().cyan(),
+                style("unknown, not decoded").dim()
+            );
+        }
+    }
+
+    match args.output {
+        Some(DataFormat::Hex) => {
+            println!("{}", style("Data:").bold().cyan());
+            for (i, chunk) in data.chunks(32).enumerate() {
+                println!(
+                    "  {} {}",
+                    style(format!("{:08x}", i * 32)).dim(),
+                    chunk
+                        .iter()
+                        .map(|byte| format!("{:02x}", byte))
+                        .collect::<String>()
+                );
+            }
+        }
+        Some(DataFormat::Base64) => {
+            println!("{}", style("Data:").bold().cyan());
+            println!(
+                "{}",
+                base64::engine::general_purpose::STANDARD.encode(&data)
+            );
+        }
+        Some(DataFormat::Json) | None => {}
+    }
+
+    Ok(())
+}
@@ -25,6 +25,11 @@ impl SolanaConfig {
             .unwrap()
             .to_string()
     }
+
+    /// the address book, labels keyed by address
+    pub fn address_labels(&self) -> &std::collections::HashMap<String, String> {
+        &self.address_labels
+    }
 }
 
 fn get_config_file() -> Result<PathBuf> {
@@ -37,6 +42,16 @@ fn get_config_file() -> Result<PathBuf> {
         .join("config.yml"))
 }
 
+/// directory for state this tool keeps between runs, like export cursors
+pub fn get_state_dir() -> Result<PathBuf> {
+    let home_dir =
+        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+    let state_dir = home_dir.join(".config").join("solana-cli-tool");
+    std::fs::create_dir_all(&state_dir)
+        .with_context(|| format!("Failed to create state directory {:?}", state_dir))?;
+    Ok(state_dir)
+}
+
 pub fn read_solana_config() -> Result<SolanaConfig> {
     let config_path = get_config_file()?;
     let config_file = std::fs::File::open(&config_path)
@@ -51,6 +66,14 @@ pub fn get_rpc_client() -> Result<RpcClient> {
     Ok(RpcClient::new(config.json_rpc_url))
 }
 
+/// genesis hash of mainnet-beta
+const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+
+/// whether the client talks to mainnet-beta, told apart by the genesis hash
+pub async fn is_mainnet(client: &RpcClient) -> Result<bool> {
+    Ok(client.get_genesis_hash().await?.to_string() == MAINNET_GENESIS_HASH)
+}
+
 pub fn show_config() -> anyhow::Result<()> {
     let config = crate::config::read_solana_config()?;
 
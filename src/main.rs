@@ -1,8 +1,13 @@
 use clap::Parser;
 
+pub mod account_manage;
 pub mod config;
 pub mod monitor;
+pub mod output;
+pub mod pda_manage;
+pub mod program_manage;
 pub mod spl_token_manage;
+pub mod tx_manage;
 pub mod utils;
 pub mod wallet_manage;
 
@@ -17,6 +22,18 @@ enum Commands {
     SplToken(spl_token_manage::SplTokenMange),
     /// monitor
     Monitor(monitor::MonitorArgs),
+    /// Transaction operations
+    #[command(subcommand)]
+    Tx(tx_manage::TxManage),
+    /// Program deployment and management
+    #[command(subcommand)]
+    Program(program_manage::ProgramManage),
+    /// Account inspection
+    #[command(subcommand)]
+    Account(account_manage::AccountManage),
+    /// Program derived addresses
+    #[command(subcommand)]
+    Pda(pda_manage::PdaManage),
 }
 
 impl Commands {
@@ -29,6 +46,14 @@ impl Commands {
                 spl_token_manage::handle_spl_token_manage(spl_token_manage).await
             }
             Commands::Monitor(monitor_args) => monitor::run_monitor(monitor_args).await,
+            Commands::Tx(tx_manage) => tx_manage::handle_tx_manage(tx_manage).await,
+            Commands::Program(program_manage) => {
+                program_manage::handle_program_manage(program_manage).await
+            }
+            Commands::Account(account_manage) => {
+                account_manage::handle_account_manage(account_manage).await
+            }
+            Commands::Pda(pda_manage) => pda_manage::handle_pda_manage(pda_manage),
         }
     }
 }
@@ -37,6 +62,17 @@ impl Commands {
 async fn main() {
     let cmd = Commands::parse();
     if let Err(e) = cmd.run().await {
+        // the status was already printed, only the exit code is left
+        if let Some(tx_manage::status::StatusExit(code)) = e.downcast_ref() {
+            std::process::exit(*code);
+        }
         eprintln!("Error: {:?}", e);
+        std::process::exit(1);
     }
 }
+
+#[test]
+fn verify_cli() {
+    use clap::CommandFactory;
+    Commands::command().debug_assert();
+}
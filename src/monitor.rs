@@ -121,7 +121,7 @@ impl Monitor {
 
                     let timestamp = if let Some(block_time) = tx.block_time {
                         DateTime::<Utc>::from_timestamp(block_time, 0)
-                            .unwrap_or_else(|| Utc::now())
+                            .unwrap_or_else(Utc::now)
                             .with_timezone(&Local)
                             .format("%Y-%m-%d %H:%M:%S")
                             .to_string()
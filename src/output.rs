@@ -0,0 +1,29 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// How list-like command output is rendered
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable, colored output
+    #[default]
+    Table,
+    /// Pretty printed JSON
+    Json,
+    /// Comma separated values with a header row
+    Csv,
+}
+
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// write the rows as csv to stdout, the header comes from the field names
+pub fn print_csv<T: Serialize>(rows: impl IntoIterator<Item = T>) -> anyhow::Result<()> {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use clap::Parser;
+
+pub mod check;
+pub mod derive;
+pub mod shortcuts;
+
+#[derive(Parser, Debug)]
+pub enum PdaManage {
+    /// derive a program derived address from its seeds
+    Derive(derive::DeriveArgs),
+    /// verify that an address is the PDA of a program and seeds
+    Check(check::CheckArgs),
+    /// associated token account of an owner and mint
+    Ata(shortcuts::AtaArgs),
+    /// Metaplex metadata account of a mint
+    Metadata(shortcuts::MetaplexArgs),
+    /// Metaplex master edition account of a mint
+    Edition(shortcuts::MetaplexArgs),
+    /// ProgramData account of an upgradeable program
+    ProgramData(shortcuts::ProgramDataArgs),
+}
+
+pub fn handle_pda_manage(pda_manage: &PdaManage) -> anyhow::Result<()> {
+    match pda_manage {
+        PdaManage::Derive(args) => derive::handle_derive(args),
+        PdaManage::Check(args) => check::handle_check(args),
+        PdaManage::Ata(args) => shortcuts::handle_ata(args),
+        PdaManage::Metadata(args) => shortcuts::handle_metadata(args),
+        PdaManage::Edition(args) => shortcuts::handle_edition(args),
+        PdaManage::ProgramData(args) => shortcuts::handle_program_data(args),
+    }
+}
@@ -0,0 +1,66 @@
+use crate::pda_manage::derive::{derive, parse_seeds};
+use clap::Parser;
+use console::style;
+use solana_sdk::pubkey::Pubkey;
+
+/// verify that an address is the PDA of a program and seeds
+#[derive(Parser, Debug)]
+pub struct CheckArgs {
+    /// Address to verify
+    pub address: String,
+
+    /// Program id the address should be derived for
+    pub program_id: String,
+
+    /// Seeds as kind:value, see `pda derive --help` for the kinds
+    pub seeds: Vec<String>,
+}
+
+pub fn handle_check(args: &CheckArgs) -> anyhow::Result<()> {
+    let address: Pubkey = args.address.parse()?;
+    let program_id: Pubkey = args.program_id.parse()?;
+    let seeds = parse_seeds(&args.seeds)?;
+
+    let (canonical, bump) = derive(&seeds, &program_id)?;
+    if canonical == address {
+        println!(
+            "{} {} with the canonical bump {}",
+            style("✔ Match:").green().bold(),
+            style(address).yellow(),
+            style(bump).yellow()
+        );
+        return Ok(());
+    }
+
+    // programs may store a lower bump, those addresses are valid too
+    let seed_slices = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    let other_bump = (0..bump).rev().find(|bump| {
+        let mut with_bump = seed_slices.clone();
+        let bump = [*bump];
+        with_bump.push(&bump);
+        Pubkey::create_program_address(&with_bump, &program_id) == Ok(address)
+    });
+    match other_bump {
+        Some(other_bump) => {
+            println!(
+                "{} {} with the non-canonical bump {}",
+                style("⚠ Match:").yellow().bold(),
+                style(address).yellow(),
+                style(other_bump).yellow()
+            );
+            println!(
+                "{} {} (bump {})",
+                style("Canonical address:").bold().cyan(),
+                style(canonical).yellow(),
+                style(bump).yellow()
+            );
+            Ok(())
+        }
+        None => anyhow::bail!(
+            "{} is not derived from these seeds, expected {} (bump {})",
+            address,
+            canonical,
+            bump
+        ),
+    }
+}
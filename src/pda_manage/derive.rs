@@ -0,0 +1,134 @@
+use crate::utils::decode_hex;
+use base64::Engine;
+use clap::Parser;
+use console::style;
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+
+/// derive a program derived address from its seeds
+#[derive(Parser, Debug)]
+pub struct DeriveArgs {
+    /// Program id the address is derived for
+    pub program_id: String,
+
+    /// Seed as kind:value, one of str, pubkey, hex, base58, base64, u8,
+    /// u16le, u16be, u32le, u32be, u64le, u64be, u128le, u128be, i64le
+    #[clap(short, long)]
+    pub seed: Vec<String>,
+}
+
+fn int<T: std::str::FromStr>(seed: &str, value: &str) -> anyhow::Result<T> {
+    value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid seed {}", seed))
+}
+
+/// parse a `kind:value` seed into its bytes
+pub fn parse_seed(seed: &str) -> anyhow::Result<Vec<u8>> {
+    let (kind, value) = seed
+        .split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("Expected kind:value, got {}", seed))?;
+    let bytes = match kind {
+        "str" | "string" => value.as_bytes().to_vec(),
+        "pubkey" => value.parse::<Pubkey>()?.to_bytes().to_vec(),
+        "hex" => decode_hex(value)?,
+        "base58" => bs58::decode(value).into_vec()?,
+        "base64" => base64::engine::general_purpose::STANDARD.decode(value)?,
+        "u8" => vec![int::<u8>(seed, value)?],
+        "u16le" => int::<u16>(seed, value)?.to_le_bytes().to_vec(),
+        "u16be" => int::<u16>(seed, value)?.to_be_bytes().to_vec(),
+        "u32le" => int::<u32>(seed, value)?.to_le_bytes().to_vec(),
+        "u32be" => int::<u32>(seed, value)?.to_be_bytes().to_vec(),
+        "u64le" => int::<u64>(seed, value)?.to_le_bytes().to_vec(),
+        "u64be" => int::<u64>(seed, value)?.to_be_bytes().to_vec(),
+        "u128le" => int::<u128>(seed, value)?.to_le_bytes().to_vec(),
+        "u128be" => int::<u128>(seed, value)?.to_be_bytes().to_vec(),
+        "i64le" => int::<i64>(seed, value)?.to_le_bytes().to_vec(),
+        _ => anyhow::bail!("Unknown seed kind {}", kind),
+    };
+    if bytes.len() > MAX_SEED_LEN {
+        anyhow::bail!(
+            "Seed {} is {} bytes, at most {} are allowed",
+            seed,
+            bytes.len(),
+            MAX_SEED_LEN
+        );
+    }
+    Ok(bytes)
+}
+
+/// parse every seed, leaving room for the bump seed
+pub fn parse_seeds(seeds: &[String]) -> anyhow::Result<Vec<Vec<u8>>> {
+    if seeds.len() >= MAX_SEEDS {
+        anyhow::bail!("At most {} seeds are allowed", MAX_SEEDS - 1);
+    }
+    seeds.iter().map(|seed| parse_seed(seed)).collect()
+}
+
+/// the canonical address and bump for the seeds
+pub fn derive(seeds: &[Vec<u8>], program_id: &Pubkey) -> anyhow::Result<(Pubkey, u8)> {
+    let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    Pubkey::try_find_program_address(&seeds, program_id)
+        .ok_or_else(|| anyhow::anyhow!("No valid bump found for these seeds"))
+}
+
+pub fn print_pda(seeds: &[Vec<u8>], program_id: &Pubkey) -> anyhow::Result<()> {
+    let (address, bump) = derive(seeds, program_id)?;
+    println!(
+        "{} {}",
+        style("Address:").bold().cyan(),
+        style(address).yellow()
+    );
+    println!("{} {}", style("Bump:").bold().cyan(), style(bump).yellow());
+    println!(
+        "{} {}",
+        style("Program:").bold().cyan(),
+        style(program_id).yellow()
+    );
+    for (i, seed) in seeds.iter().enumerate() {
+        println!(
+            "  {} {}",
+            style(format!("seed #{}:", i)).dim(),
+            seed.iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>()
+        );
+    }
+    Ok(())
+}
+
+pub fn handle_derive(args: &DeriveArgs) -> anyhow::Result<()> {
+    let program_id: Pubkey = args.program_id.parse()?;
+    let seeds = parse_seeds(&args.seed)?;
+    print_pda(&seeds, &program_id)
+}
+
+#[test]
+fn test_parse_seed() {
+    let owner = Pubkey::new_unique();
+    assert_eq!(parse_seed("str:vault").unwrap(), b"vault");
+    assert_eq!(
+        parse_seed(&format!("pubkey:{}", owner)).unwrap(),
+        owner.to_bytes()
+    );
+    assert_eq!(parse_seed("u64le:5").unwrap(), 5u64.to_le_bytes());
+    assert_eq!(parse_seed("u16be:258").unwrap(), [1, 2]);
+    assert_eq!(parse_seed("hex:0aff").unwrap(), [10, 255]);
+    assert!(parse_seed("hex:0af").is_err());
+    assert!(parse_seed("u8:256").is_err());
+    assert!(parse_seed("vault").is_err());
+    assert!(parse_seed(&format!("str:{}", "x".repeat(33))).is_err());
+
+    // the associated token account is a PDA of the ATA program
+    let mint = Pubkey::new_unique();
+    let seeds = [
+        owner.to_bytes().to_vec(),
+        spl_token::id().to_bytes().to_vec(),
+        mint.to_bytes().to_vec(),
+    ];
+    assert_eq!(
+        derive(&seeds, &spl_associated_token_account::id())
+            .unwrap()
+            .0,
+        spl_associated_token_account::get_associated_token_address(&owner, &mint)
+    );
+}
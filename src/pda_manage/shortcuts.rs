@@ -0,0 +1,84 @@
+use crate::pda_manage::derive::print_pda;
+use crate::spl_token_manage::metadata::MPL_TOKEN_METADATA_ID;
+use clap::Parser;
+use solana_sdk::bpf_loader_upgradeable;
+use solana_sdk::pubkey::Pubkey;
+
+/// associated token account of an owner and mint
+#[derive(Parser, Debug)]
+pub struct AtaArgs {
+    /// Wallet owning the token account
+    pub owner: String,
+
+    /// Token mint address
+    pub mint: String,
+
+    /// Derive for a Token-2022 mint
+    #[clap(long)]
+    pub token_2022: bool,
+}
+
+/// Metaplex metadata or master edition account of a mint
+#[derive(Parser, Debug)]
+pub struct MetaplexArgs {
+    /// Token mint address
+    pub mint: String,
+}
+
+/// ProgramData account of an upgradeable program
+#[derive(Parser, Debug)]
+pub struct ProgramDataArgs {
+    /// Program id
+    pub program_id: String,
+}
+
+pub fn handle_ata(args: &AtaArgs) -> anyhow::Result<()> {
+    let owner: Pubkey = args.owner.parse()?;
+    let mint: Pubkey = args.mint.parse()?;
+    let token_program = if args.token_2022 {
+        spl_token_2022::id()
+    } else {
+        spl_token::id()
+    };
+    print_pda(
+        &[
+            owner.to_bytes().to_vec(),
+            token_program.to_bytes().to_vec(),
+            mint.to_bytes().to_vec(),
+        ],
+        &spl_associated_token_account::id(),
+    )
+}
+
+pub fn handle_metadata(args: &MetaplexArgs) -> anyhow::Result<()> {
+    let mint: Pubkey = args.mint.parse()?;
+    print_pda(
+        &[
+            b"metadata".to_vec(),
+            MPL_TOKEN_METADATA_ID.to_bytes().to_vec(),
+            mint.to_bytes().to_vec(),
+        ],
+        &MPL_TOKEN_METADATA_ID,
+    )
+}
+
+pub fn handle_edition(args: &MetaplexArgs) -> anyhow::Result<()> {
+    let mint: Pubkey = args.mint.parse()?;
+    print_pda(
+        &[
+            b"metadata".to_vec(),
+            MPL_TOKEN_METADATA_ID.to_bytes().to_vec(),
+            mint.to_bytes().to_vec(),
+            b"edition".to_vec(),
+        ],
+        &MPL_TOKEN_METADATA_ID,
+    )
+}
+
+pub fn handle_program_data(args: &ProgramDataArgs) -> anyhow::Result<()> {
+    let program_id: Pubkey = args.program_id.parse()?;
+    print_pda(
+        &[program_id.to_bytes().to_vec()],
+        &bpf_loader_upgradeable::id(),
+    )
+}
@@ -0,0 +1,50 @@
+use clap::Parser;
+
+pub mod accounts;
+pub mod buffers;
+pub mod call;
+pub mod close;
+pub mod deploy;
+pub mod dump;
+pub mod idl;
+pub mod set_upgrade_authority;
+pub mod show;
+pub mod upgrade;
+
+#[derive(Parser, Debug)]
+pub enum ProgramManage {
+    /// deploy a program through the upgradeable loader
+    Deploy(deploy::DeployArgs),
+    /// upgrade a deployed program with a new build
+    Upgrade(upgrade::UpgradeArgs),
+    /// show the state of a program or buffer
+    Show(show::ShowArgs),
+    /// change or remove the upgrade authority of a program or buffer
+    SetUpgradeAuthority(set_upgrade_authority::SetUpgradeAuthorityArgs),
+    /// close a program or buffer and reclaim its rent
+    Close(close::CloseArgs),
+    /// save the deployed program of a program or buffer to a file
+    Dump(dump::DumpArgs),
+    /// list the buffers held by the keystore wallets, and close them
+    Buffers(buffers::BuffersArgs),
+    /// call an instruction of an Anchor program from its IDL
+    Call(call::CallArgs),
+    /// fetch and decode every account of one Anchor account type
+    Accounts(accounts::AccountsArgs),
+}
+
+pub async fn handle_program_manage(program_manage: &ProgramManage) -> anyhow::Result<()> {
+    match program_manage {
+        ProgramManage::Deploy(args) => deploy::handle_deploy(args).await,
+        ProgramManage::Upgrade(args) => upgrade::handle_upgrade(args).await,
+        ProgramManage::Show(args) => show::handle_show(args).await,
+        ProgramManage::SetUpgradeAuthority(args) => {
+            set_upgrade_authority::handle_set_upgrade_authority(args).await
+        }
+        ProgramManage::Close(args) => close::handle_close(args).await,
+        ProgramManage::Dump(args) => dump::handle_dump(args).await,
+        ProgramManage::Buffers(args) => buffers::handle_buffers(args).await,
+        ProgramManage::Call(args) => call::handle_call(args).await,
+        ProgramManage::Accounts(args) => accounts::handle_accounts(args).await,
+    }
+}
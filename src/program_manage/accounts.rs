@@ -0,0 +1,121 @@
+use crate::config::get_rpc_client;
+use crate::output::{print_csv, print_json, OutputFormat};
+use crate::program_manage::idl::{decode_account, load_idl};
+use clap::Parser;
+use console::style;
+use serde::Serialize;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+
+/// fetch and decode every account of one Anchor account type
+#[derive(Parser, Debug)]
+pub struct AccountsArgs {
+    /// Program id
+    pub program_id: String,
+
+    /// Account type name, as in the IDL
+    #[clap(short, long = "type")]
+    pub account_type: String,
+
+    /// IDL file (optional, fetched from chain if not provided)
+    #[clap(long)]
+    pub idl: Option<String>,
+
+    /// Output format
+    #[clap(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+}
+
+#[derive(Debug, Serialize)]
+struct AccountRow {
+    address: String,
+    lamports: u64,
+    data: serde_json::Value,
+}
+
+/// csv cells can not nest, the data goes in as a json string
+#[derive(Debug, Serialize)]
+struct AccountCsvRow {
+    address: String,
+    lamports: u64,
+    data: String,
+}
+
+pub async fn handle_accounts(args: &AccountsArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let program_id: Pubkey = args.program_id.parse()?;
+    let idl = load_idl(&client, args.idl.as_deref(), Some(&program_id)).await?;
+    let account_type = idl.account(&args.account_type).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown account type {}, the IDL has: {}",
+            args.account_type,
+            idl.accounts
+                .iter()
+                .map(|account| account.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+
+    let accounts = client
+        .get_program_accounts_with_config(
+            &program_id,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    0,
+                    account_type.discriminator(),
+                ))]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(client.commitment()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let mut rows = vec![];
+    for (address, account) in accounts {
+        match decode_account(&idl, &account.data) {
+            Ok((_, data)) => rows.push(AccountRow {
+                address: address.to_string(),
+                lamports: account.lamports,
+                data,
+            }),
+            Err(e) => eprintln!(
+                "{} {}",
+                style("WARNING:").yellow().bold(),
+                style(format!("Failed to decode {}: {}", address, e)).yellow()
+            ),
+        }
+    }
+    rows.sort_by(|a, b| a.address.cmp(&b.address));
+
+    match args.output {
+        OutputFormat::Json => print_json(&rows)?,
+        OutputFormat::Csv => print_csv(rows.into_iter().map(|row| AccountCsvRow {
+            address: row.address,
+            lamports: row.lamports,
+            data: row.data.to_string(),
+        }))?,
+        OutputFormat::Table => {
+            println!(
+                "{} {} {}",
+                style(rows.len()).yellow().bold(),
+                style(&account_type.name).cyan().bold(),
+                style("accounts").cyan()
+            );
+            for row in &rows {
+                println!("\n{}", style(&row.address).yellow());
+                for line in serde_json::to_string_pretty(&row.data)?.lines() {
+                    println!("  {}", line);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use crate::config::get_rpc_client;
+use crate::utils::{confirm, print_total_recovered, send_close_batch};
+use crate::wallet_manage::list_wallets::unique_keystore_wallets;
+use clap::Parser;
+use console::style;
+use solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::RpcKeyedAccount;
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// list the buffers held by the keystore wallets, and close them
+#[derive(Parser, Debug)]
+pub struct BuffersArgs {
+    /// Close the buffers and reclaim their rent to the authority
+    #[clap(long)]
+    pub close: bool,
+
+    /// Number of buffers closed per transaction
+    #[clap(long, default_value = "10")]
+    pub batch_size: usize,
+
+    /// Skip the confirmation prompt
+    #[clap(short, long)]
+    pub yes: bool,
+}
+
+/// A buffer account left behind by a deploy
+struct Buffer {
+    address: Pubkey,
+    size: Option<u64>,
+    lamports: u64,
+}
+
+/// every buffer whose authority is `authority`
+async fn find_buffers(client: &RpcClient, authority: &Pubkey) -> anyhow::Result<Vec<Buffer>> {
+    // the serialized buffer header is the enum tag followed by the authority
+    let header = bincode::serialize(&UpgradeableLoaderState::Buffer {
+        authority_address: Some(*authority),
+    })?;
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0, header,
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(UiDataSliceConfig {
+                offset: 0,
+                length: 0,
+            }),
+            commitment: Some(client.commitment()),
+            min_context_slot: None,
+        },
+        with_context: None,
+        sort_results: None,
+    };
+
+    // the raw response keeps the account size the data slice hides
+    let accounts: Vec<RpcKeyedAccount> = client
+        .send(
+            RpcRequest::GetProgramAccounts,
+            serde_json::json!([bpf_loader_upgradeable::id().to_string(), config]),
+        )
+        .await?;
+    accounts
+        .into_iter()
+        .map(|keyed_account| {
+            Ok(Buffer {
+                address: keyed_account.pubkey.parse()?,
+                size: keyed_account.account.space,
+                lamports: keyed_account.account.lamports,
+            })
+        })
+        .collect()
+}
+
+/// close the buffers of one wallet in batches, returning the lamports recovered
+async fn close_buffers(
+    client: &RpcClient,
+    wallet: &Keypair,
+    buffers: &[Buffer],
+    batch_size: usize,
+) -> anyhow::Result<u64> {
+    let authority = wallet.pubkey();
+    let mut recovered = 0;
+    for batch in buffers.chunks(batch_size.max(1)) {
+        let instructions = batch
+            .iter()
+            .map(|buffer| {
+                bpf_loader_upgradeable::close_any(
+                    &buffer.address,
+                    &authority,
+                    Some(&authority),
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let lamports = batch.iter().map(|buffer| buffer.lamports).sum::<u64>();
+        recovered += send_close_batch(
+            client,
+            wallet,
+            &instructions,
+            &format!("{} buffers", batch.len()),
+            lamports,
+        )
+        .await?;
+    }
+
+    Ok(recovered)
+}
+
+pub async fn handle_buffers(args: &BuffersArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+
+    let wallets = unique_keystore_wallets()?;
+
+    let mut found = vec![];
+    for wallet in &wallets {
+        match find_buffers(&client, &wallet.pubkey()).await {
+            Ok(buffers) if buffers.is_empty() => {}
+            Ok(buffers) => found.push((wallet, buffers)),
+            Err(e) => println!(
+                "{} {}",
+                style("ERROR:").red().bold(),
+                style(format!(
+                    "Buffer lookup of {} failed: {:?}",
+                    wallet.pubkey(),
+                    e
+                ))
+                .red()
+            ),
+        }
+    }
+
+    if found.is_empty() {
+        println!("{}", style("No buffers found").dim().italic());
+        return Ok(());
+    }
+
+    for (wallet, buffers) in &found {
+        println!(
+            "\n{} {}",
+            style("Authority:").bold().cyan(),
+            style(wallet.pubkey()).yellow()
+        );
+        for buffer in buffers {
+            println!(
+                "  → {} {:>10} {} SOL",
+                style(buffer.address).cyan(),
+                buffer
+                    .size
+                    .map(|size| format!("{} bytes", size))
+                    .unwrap_or_else(|| "? bytes".to_string()),
+                style(lamports_to_sol(buffer.lamports)).green()
+            );
+        }
+    }
+
+    let count = found
+        .iter()
+        .map(|(_, buffers)| buffers.len())
+        .sum::<usize>();
+    let locked = found
+        .iter()
+        .flat_map(|(_, buffers)| buffers.iter())
+        .map(|buffer| buffer.lamports)
+        .sum::<u64>();
+    println!(
+        "\n{} {} buffers, {} {}",
+        style("Total:").bold().cyan(),
+        style(count).yellow(),
+        style(lamports_to_sol(locked)).green().bold(),
+        style("SOL").cyan()
+    );
+
+    if !args.close {
+        return Ok(());
+    }
+    if !args.yes
+        && !confirm(&format!(
+            "Close {} buffers and reclaim {} SOL?",
+            count,
+            lamports_to_sol(locked)
+        ))?
+    {
+        println!("{}", style("Aborted").yellow());
+        return Ok(());
+    }
+
+    let mut total = 0;
+    for (wallet, buffers) in &found {
+        total += close_buffers(&client, wallet, buffers, args.batch_size).await?;
+    }
+    print_total_recovered(total);
+
+    Ok(())
+}
@@ -0,0 +1,457 @@
+use crate::config::get_rpc_client;
+use crate::program_manage::idl::{
+    defined, encode, load_idl, lookup, Idl, IdlAccountItem, IdlInstruction, IdlInstructionAccount,
+    IdlPda, IdlSeed,
+};
+use crate::tx_manage::decode::{print_instruction, DecodedInstruction};
+use crate::tx_manage::send::simulate;
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use heck::ToSnakeCase;
+use serde_json::{Map, Value};
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_sdk::{system_program, sysvar};
+use std::collections::HashMap;
+
+/// call an instruction of an Anchor program from its IDL
+#[derive(Parser, Debug)]
+pub struct CallArgs {
+    /// Instruction name, as in the IDL
+    pub instruction: String,
+
+    /// IDL file (optional, fetched from chain if not provided)
+    #[clap(long)]
+    pub idl: Option<String>,
+
+    /// Program id (optional if the IDL has an address)
+    #[clap(long)]
+    pub program_id: Option<String>,
+
+    /// Arguments as a json object keyed by name, or a json array in order
+    #[clap(long, default_value = "{}")]
+    pub args: String,
+
+    /// Account as name=address, can be repeated or comma separated
+    #[clap(long, value_delimiter = ',')]
+    pub accounts: Vec<String>,
+
+    /// Payer keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    /// Extra signer keypair path, can be repeated
+    #[clap(long)]
+    pub signer: Vec<String>,
+
+    /// Simulate the instruction instead of sending it
+    #[clap(long)]
+    pub simulate: bool,
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.to_snake_case() == b.to_snake_case()
+}
+
+/// every account of the instruction with its path, groups joined by dots
+fn flatten<'a>(
+    items: &'a [IdlAccountItem],
+    prefix: &str,
+    out: &mut Vec<(String, &'a IdlInstructionAccount)>,
+) {
+    for item in items {
+        match item {
+            IdlAccountItem::Composite { name, accounts } => {
+                flatten(accounts, &format!("{}{}.", prefix, name), out)
+            }
+            IdlAccountItem::Single(account) => {
+                out.push((format!("{}{}", prefix, account.name), account))
+            }
+        }
+    }
+}
+
+/// programs and sysvars older IDLs name without an address
+fn well_known(name: &str) -> Option<Pubkey> {
+    Some(match name.to_snake_case().as_str() {
+        "system_program" => system_program::id(),
+        "token_program" => spl_token::id(),
+        "token2022_program" | "token_2022_program" => spl_token_2022::id(),
+        "associated_token_program" => spl_associated_token_account::id(),
+        "rent" => sysvar::rent::id(),
+        "clock" => sysvar::clock::id(),
+        "instructions" | "instructions_sysvar" => sysvar::instructions::id(),
+        _ => return None,
+    })
+}
+
+/// the instruction arguments as an object, an array is matched by position
+fn parse_args(instruction: &IdlInstruction, args: &str) -> anyhow::Result<Map<String, Value>> {
+    match serde_json::from_str(args).map_err(|e| anyhow::anyhow!("Invalid --args: {}", e))? {
+        Value::Object(object) => Ok(object),
+        Value::Array(values) => {
+            if values.len() != instruction.args.len() {
+                anyhow::bail!(
+                    "{} takes {} arguments, got {}",
+                    instruction.name,
+                    instruction.args.len(),
+                    values.len()
+                );
+            }
+            Ok(instruction
+                .args
+                .iter()
+                .map(|arg| arg.name.clone())
+                .zip(values)
+                .collect())
+        }
+        _ => anyhow::bail!("--args must be a json object or array"),
+    }
+}
+
+/// the instruction data, discriminator followed by the borsh encoded arguments
+pub fn encode_instruction_data(
+    idl: &Idl,
+    instruction: &IdlInstruction,
+    args: &Map<String, Value>,
+) -> anyhow::Result<Vec<u8>> {
+    let mut data = instruction.discriminator();
+    for arg in &instruction.args {
+        let value = lookup(args, &arg.name)
+            .ok_or_else(|| anyhow::anyhow!("Missing argument {}", arg.name))?;
+        encode(idl, &arg.ty, value, &mut data)
+            .map_err(|e| anyhow::anyhow!("Argument {}: {}", arg.name, e))?;
+    }
+    Ok(data)
+}
+
+/// resolves instruction accounts, PDAs may depend on arguments and other accounts
+pub struct AccountResolver<'a> {
+    pub idl: &'a Idl,
+    pub instruction: &'a IdlInstruction,
+    pub args: &'a Map<String, Value>,
+    pub program_id: Pubkey,
+    pub accounts: Vec<(String, &'a IdlInstructionAccount)>,
+    pub resolved: HashMap<String, Pubkey>,
+}
+
+impl<'a> AccountResolver<'a> {
+    pub fn new(
+        idl: &'a Idl,
+        instruction: &'a IdlInstruction,
+        args: &'a Map<String, Value>,
+        program_id: Pubkey,
+    ) -> Self {
+        let mut accounts = vec![];
+        flatten(&instruction.accounts, "", &mut accounts);
+        Self {
+            idl,
+            instruction,
+            args,
+            program_id,
+            accounts,
+            resolved: HashMap::new(),
+        }
+    }
+
+    /// the path of the account `name` refers to, by full path or by its own name
+    fn account_path(&self, name: &str) -> Option<&str> {
+        self.accounts
+            .iter()
+            .map(|(path, _)| path.as_str())
+            .find(|path| {
+                same_name(path, name)
+                    || path
+                        .rsplit('.')
+                        .next()
+                        .is_some_and(|leaf| same_name(leaf, name))
+            })
+    }
+
+    /// pin an account to an address given on the command line
+    pub fn set(&mut self, name: &str, address: Pubkey) -> anyhow::Result<()> {
+        let path = self
+            .account_path(name)
+            .ok_or_else(|| anyhow::anyhow!("{} has no account {}", self.instruction.name, name))?
+            .to_string();
+        self.resolved.insert(path, address);
+        Ok(())
+    }
+
+    /// the bytes of an argument seed, `path` may go into struct fields
+    fn arg_seed(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        let mut parts = path.split('.');
+        let first = parts.next().unwrap_or_default();
+        let arg = self
+            .instruction
+            .args
+            .iter()
+            .find(|arg| same_name(&arg.name, first))
+            .ok_or_else(|| anyhow::anyhow!("Seed refers to unknown argument {}", path))?;
+        let mut ty = &arg.ty;
+        let mut value = lookup(self.args, &arg.name)
+            .ok_or_else(|| anyhow::anyhow!("Missing argument {}", arg.name))?;
+        for part in parts {
+            let field = defined(self.idl, ty)
+                .and_then(|def| def.ty["fields"].as_array())
+                .and_then(|fields| {
+                    fields.iter().find(|field| {
+                        field["name"]
+                            .as_str()
+                            .is_some_and(|name| same_name(name, part))
+                    })
+                })
+                .ok_or_else(|| anyhow::anyhow!("Seed refers to unknown field {}", path))?;
+            ty = &field["type"];
+            value = value
+                .as_object()
+                .and_then(|object| lookup(object, part))
+                .ok_or_else(|| anyhow::anyhow!("Missing argument field {}", path))?;
+        }
+
+        // strings and bytes are used as is, without the length prefix
+        match ty.as_str() {
+            Some("string") => Ok(value
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("Expected a string"))?
+                .as_bytes()
+                .to_vec()),
+            Some("bytes") => Ok(serde_json::from_value(value.clone())?),
+            _ => {
+                let mut bytes = vec![];
+                encode(self.idl, ty, value, &mut bytes)?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// the bytes of a seed, `None` while the account it needs is unresolved
+    fn seed(&self, seed: &IdlSeed) -> anyhow::Result<Option<Vec<u8>>> {
+        match seed {
+            IdlSeed::Const { value } => match value {
+                Value::String(text) => Ok(Some(text.as_bytes().to_vec())),
+                value => Ok(Some(serde_json::from_value(value.clone())?)),
+            },
+            IdlSeed::Arg { path } => self.arg_seed(path).map(Some),
+            IdlSeed::Account { path } => {
+                let Some(account_path) = self.account_path(path) else {
+                    anyhow::bail!(
+                        "Seed {} is read from account data, pass the account with --accounts",
+                        path
+                    );
+                };
+                Ok(self
+                    .resolved
+                    .get(account_path)
+                    .map(|address| address.to_bytes().to_vec()))
+            }
+        }
+    }
+
+    fn pda(&self, pda: &IdlPda) -> anyhow::Result<Option<Pubkey>> {
+        let mut seeds = vec![];
+        for seed in &pda.seeds {
+            let Some(bytes) = self.seed(seed)? else {
+                return Ok(None);
+            };
+            seeds.push(bytes);
+        }
+        let program_id = match &pda.program {
+            Some(program) => match self.seed(program)? {
+                Some(bytes) => Pubkey::try_from(bytes.as_slice())
+                    .map_err(|_| anyhow::anyhow!("Invalid PDA program seed"))?,
+                None => return Ok(None),
+            },
+            None => self.program_id,
+        };
+        let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        Ok(Some(Pubkey::find_program_address(&seeds, &program_id).0))
+    }
+
+    /// resolve every account, signers default to `payer`, returns the metas
+    pub fn resolve(&mut self, payer: &Pubkey) -> anyhow::Result<Vec<AccountMeta>> {
+        let explicit = self.resolved.keys().cloned().collect::<Vec<_>>();
+        // repeat while PDAs keep unlocking the accounts their seeds depend on
+        loop {
+            let mut progress = false;
+            for (path, account) in &self.accounts {
+                if self.resolved.contains_key(path) {
+                    continue;
+                }
+                let address = if let Some(address) = &account.address {
+                    Some(address.parse()?)
+                } else if let Some(pda) = &account.pda {
+                    self.pda(pda)?
+                } else if let Some(program) = well_known(&account.name) {
+                    Some(program)
+                } else if account.signer {
+                    Some(*payer)
+                } else if account.optional {
+                    // anchor reads the program id in place of a missing account
+                    Some(self.program_id)
+                } else {
+                    None
+                };
+                if let Some(address) = address {
+                    self.resolved.insert(path.clone(), address);
+                    progress = true;
+                }
+            }
+            if !progress {
+                break;
+            }
+        }
+
+        let missing = self
+            .accounts
+            .iter()
+            .filter(|(path, _)| !self.resolved.contains_key(path))
+            .map(|(path, _)| path.as_str())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "Missing accounts {}, pass them as --accounts name=<address>",
+                missing.join(", ")
+            );
+        }
+
+        Ok(self
+            .accounts
+            .iter()
+            .map(|(path, account)| {
+                let pubkey = self.resolved[path];
+                if account.optional && pubkey == self.program_id && !explicit.contains(path) {
+                    AccountMeta::new_readonly(pubkey, false)
+                } else if account.writable {
+                    AccountMeta::new(pubkey, account.signer)
+                } else {
+                    AccountMeta::new_readonly(pubkey, account.signer)
+                }
+            })
+            .collect())
+    }
+}
+
+pub async fn handle_call(args: &CallArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let mut signers = vec![payer.insecure_clone()];
+    for path in &args.signer {
+        signers.push(read_keypair_or_default(Some(path))?);
+    }
+
+    let program_id = args
+        .program_id
+        .as_deref()
+        .map(str::parse::<Pubkey>)
+        .transpose()?;
+    let idl = load_idl(&client, args.idl.as_deref(), program_id.as_ref()).await?;
+    let program_id = program_id
+        .or_else(|| idl.program_id())
+        .ok_or_else(|| anyhow::anyhow!("The IDL has no address, pass --program-id"))?;
+    let instruction = idl.instruction(&args.instruction).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown instruction {}, the IDL has: {}",
+            args.instruction,
+            idl.instructions
+                .iter()
+                .map(|instruction| instruction.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+
+    let call_args = parse_args(instruction, &args.args)?;
+    let data = encode_instruction_data(&idl, instruction, &call_args)?;
+    let mut resolver = AccountResolver::new(&idl, instruction, &call_args, program_id);
+    for account in &args.accounts {
+        let (name, address) = account
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected name=address, got {}", account))?;
+        resolver.set(name, address.parse()?)?;
+    }
+    let metas = resolver.resolve(&payer.pubkey())?;
+
+    print_instruction(
+        "Call",
+        &DecodedInstruction {
+            program: program_id.to_string(),
+            name: instruction.name.clone(),
+            args: Some(Value::Object(call_args.clone()).to_string()),
+            accounts: resolver
+                .accounts
+                .iter()
+                .zip(&metas)
+                .map(|((path, _), meta)| {
+                    let mut roles = vec![];
+                    if meta.is_signer {
+                        roles.push("signer");
+                    }
+                    if meta.is_writable {
+                        roles.push("writable");
+                    }
+                    let roles = if roles.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" [{}]", roles.join(", "))
+                    };
+                    (path.clone(), format!("{}{}", meta.pubkey, roles))
+                })
+                .collect(),
+            raw_data: Some(data.iter().map(|byte| format!("{:02x}", byte)).collect()),
+        },
+        0,
+    );
+
+    let instruction = Instruction {
+        program_id,
+        accounts: metas,
+        data,
+    };
+    let message = Message::new(&[instruction], Some(&payer.pubkey()));
+    let required = &message.account_keys[..message.header.num_required_signatures as usize];
+    let missing = required
+        .iter()
+        .filter(|key| !signers.iter().any(|signer| signer.pubkey() == **key))
+        .map(|key| key.to_string())
+        .collect::<Vec<_>>();
+    let signers = signers
+        .iter()
+        .filter(|signer| required.contains(&signer.pubkey()))
+        .collect::<Vec<&Keypair>>();
+
+    let mut transaction = Transaction::new_unsigned(message);
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    transaction.try_partial_sign(&signers, recent_blockhash)?;
+
+    if args.simulate {
+        // signatures are not checked so missing signers can still be tried
+        simulate(
+            &client,
+            &transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                ..Default::default()
+            },
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "No keypair for signers {}, pass them with --signer",
+            missing.join(", ")
+        );
+    }
+    let signature = client.send_and_confirm_transaction(&transaction).await?;
+    println!("\n{}", style("Successfully called program").green());
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use crate::config::get_rpc_client;
+use crate::program_manage::deploy::send_instructions;
+use crate::program_manage::show::{fetch_loader_account, LoaderAccount};
+use crate::utils::{confirm_typed, read_keypair_or_default};
+use clap::Parser;
+use console::style;
+use solana_sdk::bpf_loader_upgradeable;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// close a program or buffer and reclaim its rent
+#[derive(Parser, Debug)]
+pub struct CloseArgs {
+    /// Program id or buffer address
+    pub address: String,
+
+    /// Account receiving the reclaimed SOL (optional, the payer if not provided)
+    #[clap(long)]
+    pub recipient: Option<String>,
+
+    /// Payer keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    /// Authority keypair path (optional, the payer if not provided)
+    #[clap(long)]
+    pub authority: Option<String>,
+}
+
+pub async fn handle_close(args: &CloseArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let address: Pubkey = args.address.parse()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let authority = match &args.authority {
+        Some(path) => read_keypair_or_default(Some(path))?,
+        None => payer.insecure_clone(),
+    };
+    let recipient = match &args.recipient {
+        Some(recipient) => recipient.parse::<Pubkey>()?,
+        None => payer.pubkey(),
+    };
+
+    let (instruction, lamports) = match fetch_loader_account(&client, &address).await? {
+        LoaderAccount::Program {
+            program_data,
+            authority: current,
+            program_data_lamports,
+            ..
+        } => {
+            if current != Some(authority.pubkey()) {
+                anyhow::bail!(
+                    "{} is not the upgrade authority of {}",
+                    authority.pubkey(),
+                    address
+                );
+            }
+            if !confirm_typed(
+                "A closed program can not be redeployed at the same address.",
+                &address.to_string(),
+            )? {
+                println!("{}", style("Aborted").yellow());
+                return Ok(());
+            }
+            let instruction = bpf_loader_upgradeable::close_any(
+                &program_data,
+                &recipient,
+                Some(&authority.pubkey()),
+                Some(&address),
+            );
+            // the program account itself keeps its rent
+            (instruction, program_data_lamports)
+        }
+        LoaderAccount::Buffer {
+            authority: current,
+            lamports,
+            ..
+        } => {
+            if current != Some(authority.pubkey()) {
+                anyhow::bail!(
+                    "{} is not the authority of buffer {}",
+                    authority.pubkey(),
+                    address
+                );
+            }
+            let instruction = bpf_loader_upgradeable::close_any(
+                &address,
+                &recipient,
+                Some(&authority.pubkey()),
+                None,
+            );
+            (instruction, lamports)
+        }
+    };
+    let signature = send_instructions(&client, &[instruction], &payer, &[&authority]).await?;
+
+    println!(
+        "\n{} {} SOL to {}",
+        style("Successfully closed, reclaimed").green(),
+        style(lamports_to_sol(lamports)).green(),
+        style(recipient).yellow()
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
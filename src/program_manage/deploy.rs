@@ -0,0 +1,393 @@
+use crate::config::get_rpc_client;
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use futures::StreamExt;
+use solana_client::nonblocking::rpc_client::RpcClient;
+pub use solana_packet::PACKET_DATA_SIZE;
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, write_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use std::path::Path;
+
+/// deploy a program through the upgradeable loader
+#[derive(Parser, Debug)]
+pub struct DeployArgs {
+    /// Path of the compiled program (.so)
+    pub program: String,
+
+    /// Program keypair path, defaults to <program>-keypair.json next to the .so
+    #[clap(long)]
+    pub program_keypair: Option<String>,
+
+    /// Payer keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    /// Upgrade authority keypair path (optional, the payer if not provided)
+    #[clap(long)]
+    pub upgrade_authority: Option<String>,
+
+    /// Maximum program size in bytes, defaults to twice the program size
+    #[clap(long)]
+    pub max_data_len: Option<usize>,
+
+    /// Resume from this buffer instead of creating a new one
+    #[clap(long)]
+    pub buffer: Option<String>,
+
+    /// Number of buffer writes in flight at the same time
+    #[clap(long, default_value = "8")]
+    pub concurrency: usize,
+
+    /// Rounds of retries for failed buffer writes
+    #[clap(long, default_value = "5")]
+    pub retries: usize,
+}
+
+/// read a program file, making sure it is an ELF binary
+pub fn read_program(path: &str) -> anyhow::Result<Vec<u8>> {
+    let program =
+        std::fs::read(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?;
+    if !program.starts_with(b"\x7fELF") {
+        anyhow::bail!("{} is not a compiled program (ELF) file", path);
+    }
+    Ok(program)
+}
+
+/// sign and send with every distinct signer, a payer that is also the
+/// authority only signs once
+pub async fn send_instructions(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> anyhow::Result<Signature> {
+    let mut unique: Vec<&Keypair> = vec![payer];
+    for signer in signers {
+        if !unique.iter().any(|known| known.pubkey() == signer.pubkey()) {
+            unique.push(signer);
+        }
+    }
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &unique,
+        recent_blockhash,
+    );
+    Ok(client.send_and_confirm_transaction(&transaction).await?)
+}
+
+/// how many program bytes fit into a single write transaction
+fn max_chunk_size(payer: &Pubkey, buffer: &Pubkey, authority: &Pubkey) -> usize {
+    let write = bpf_loader_upgradeable::write(buffer, authority, 0, vec![]);
+    let message = Message::new_with_blockhash(&[write], Some(payer), &Hash::default());
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.signatures =
+        vec![Signature::default(); transaction.message.header.num_required_signatures as usize];
+    let size = bincode::serialized_size(&transaction).unwrap_or(PACKET_DATA_SIZE as u64) as usize;
+    // the data length prefix grows by up to two bytes once it is filled
+    PACKET_DATA_SIZE.saturating_sub(size + 2)
+}
+
+/// create a buffer for `program`, or check that an existing one can be resumed
+pub async fn create_or_resume_buffer(
+    client: &RpcClient,
+    payer: &Keypair,
+    authority: &Keypair,
+    program: &[u8],
+    buffer: Option<&str>,
+) -> anyhow::Result<Pubkey> {
+    if let Some(buffer) = buffer {
+        let buffer: Pubkey = buffer.parse()?;
+        let account = client.get_account(&buffer).await?;
+        if account.owner != bpf_loader_upgradeable::id() {
+            anyhow::bail!("{} is not a buffer account", buffer);
+        }
+        let metadata = account
+            .data
+            .get(..UpgradeableLoaderState::size_of_buffer_metadata())
+            .ok_or_else(|| anyhow::anyhow!("{} is not a buffer account", buffer))?;
+        match bincode::deserialize::<UpgradeableLoaderState>(metadata)? {
+            UpgradeableLoaderState::Buffer { authority_address } => {
+                if authority_address != Some(authority.pubkey()) {
+                    anyhow::bail!("Buffer {} is not owned by {}", buffer, authority.pubkey());
+                }
+            }
+            _ => anyhow::bail!("{} is not a buffer account", buffer),
+        }
+        if account.data.len() != UpgradeableLoaderState::size_of_buffer(program.len()) {
+            anyhow::bail!(
+                "Buffer {} was created for a program of another size",
+                buffer
+            );
+        }
+        println!(
+            "{} {}",
+            style("Resuming buffer").bold().cyan(),
+            style(buffer).yellow()
+        );
+        return Ok(buffer);
+    }
+
+    let buffer = Keypair::new();
+    let lamports = client
+        .get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_buffer(
+            program.len(),
+        ))
+        .await?;
+    let instructions = bpf_loader_upgradeable::create_buffer(
+        &payer.pubkey(),
+        &buffer.pubkey(),
+        &authority.pubkey(),
+        lamports,
+        program.len(),
+    )?;
+    send_instructions(client, &instructions, payer, &[&buffer]).await?;
+    println!(
+        "{} {} ({} SOL)",
+        style("Created buffer").bold().cyan(),
+        style(buffer.pubkey()).yellow(),
+        style(lamports_to_sol(lamports)).green()
+    );
+    Ok(buffer.pubkey())
+}
+
+/// write every chunk of the program that differs from the buffer content,
+/// in parallel and retrying the failed writes
+pub async fn write_buffer(
+    client: &RpcClient,
+    payer: &Keypair,
+    authority: &Keypair,
+    buffer: &Pubkey,
+    program: &[u8],
+    concurrency: usize,
+    retries: usize,
+) -> anyhow::Result<()> {
+    let chunk_size = max_chunk_size(&payer.pubkey(), buffer, &authority.pubkey());
+    let written = client.get_account(buffer).await?.data;
+    let written = written
+        .get(UpgradeableLoaderState::size_of_buffer_metadata()..)
+        .ok_or_else(|| anyhow::anyhow!("{} is not a buffer account", buffer))?;
+
+    let mut pending = program
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(i, chunk)| (i * chunk_size, chunk))
+        .filter(|(offset, chunk)| written.get(*offset..*offset + chunk.len()) != Some(*chunk))
+        .collect::<Vec<_>>();
+    let total = program.len().div_ceil(chunk_size);
+    println!(
+        "{} {} of {} chunks ({} bytes each)",
+        style("Writing").bold().cyan(),
+        style(pending.len()).yellow(),
+        total,
+        chunk_size
+    );
+
+    for round in 0..=retries {
+        if pending.is_empty() {
+            break;
+        }
+        if round > 0 {
+            println!(
+                "{} {} failed writes (round {} of {})",
+                style("Retrying").yellow(),
+                pending.len(),
+                round,
+                retries
+            );
+        }
+
+        let results = futures::stream::iter(pending.iter().map(|(offset, chunk)| async move {
+            // a round of a large program outlives a single blockhash
+            let Ok(recent_blockhash) = client.get_latest_blockhash().await else {
+                return false;
+            };
+            let write = bpf_loader_upgradeable::write(
+                buffer,
+                &authority.pubkey(),
+                *offset as u32,
+                chunk.to_vec(),
+            );
+            let signers: Vec<&Keypair> = if payer.pubkey() == authority.pubkey() {
+                vec![payer]
+            } else {
+                vec![payer, authority]
+            };
+            let transaction = Transaction::new_signed_with_payer(
+                &[write],
+                Some(&payer.pubkey()),
+                &signers,
+                recent_blockhash,
+            );
+            client
+                .send_and_confirm_transaction(&transaction)
+                .await
+                .is_ok()
+        }))
+        .buffered(concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+        pending = pending
+            .into_iter()
+            .zip(results)
+            .filter(|(_, written)| !written)
+            .map(|(chunk, _)| chunk)
+            .collect();
+    }
+
+    if !pending.is_empty() {
+        anyhow::bail!(
+            "{} chunks could not be written, resume with --buffer {}",
+            pending.len(),
+            buffer
+        );
+    }
+    println!("{}", style("Buffer written").green());
+    Ok(())
+}
+
+/// the program keypair from the flag, or <program>-keypair.json next to the
+/// .so, generated on the first deploy
+fn program_keypair(program_path: &str, keypair_path: Option<&str>) -> anyhow::Result<Keypair> {
+    if let Some(path) = keypair_path {
+        return read_keypair_file(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read keypair file {}: {}", path, e));
+    }
+
+    let path = Path::new(program_path);
+    let stem = path
+        .file_stem()
+        .ok_or_else(|| anyhow::anyhow!("Invalid program path {}", program_path))?;
+    let keypair_path = path.with_file_name(format!("{}-keypair.json", stem.to_string_lossy()));
+    if keypair_path.exists() {
+        return read_keypair_file(&keypair_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read keypair file {:?}: {}", keypair_path, e));
+    }
+
+    let keypair = Keypair::new();
+    write_keypair_file(&keypair, &keypair_path)
+        .map_err(|e| anyhow::anyhow!("Failed to write keypair file {:?}: {}", keypair_path, e))?;
+    println!(
+        "{} {}",
+        style("Generated program keypair").bold().cyan(),
+        style(keypair_path.display()).yellow()
+    );
+    Ok(keypair)
+}
+
+pub async fn handle_deploy(args: &DeployArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let program = read_program(&args.program)?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let authority = match &args.upgrade_authority {
+        Some(path) => read_keypair_or_default(Some(path))?,
+        None => payer.insecure_clone(),
+    };
+    let program_keypair = program_keypair(&args.program, args.program_keypair.as_deref())?;
+    let program_id = program_keypair.pubkey();
+
+    let max_data_len = args.max_data_len.unwrap_or(program.len() * 2);
+    if max_data_len < program.len() {
+        anyhow::bail!(
+            "--max-data-len {} is smaller than the program ({} bytes)",
+            max_data_len,
+            program.len()
+        );
+    }
+
+    if client
+        .get_account_with_commitment(&program_id, client.commitment())
+        .await?
+        .value
+        .is_some()
+    {
+        anyhow::bail!(
+            "Program {} already exists, upgrade it instead of deploying",
+            program_id
+        );
+    }
+
+    // the buffer is refunded on deploy, program data stays locked
+    let program_lamports = client
+        .get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_program())
+        .await?;
+    let program_data_lamports = client
+        .get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_programdata(
+            max_data_len,
+        ))
+        .await?;
+    println!(
+        "{} {} bytes, max {} bytes, about {} SOL of rent",
+        style("Deploying").bold().cyan(),
+        style(program.len()).yellow(),
+        style(max_data_len).yellow(),
+        style(lamports_to_sol(program_lamports + program_data_lamports)).green()
+    );
+
+    let buffer = create_or_resume_buffer(
+        &client,
+        &payer,
+        &authority,
+        &program,
+        args.buffer.as_deref(),
+    )
+    .await?;
+    write_buffer(
+        &client,
+        &payer,
+        &authority,
+        &buffer,
+        &program,
+        args.concurrency,
+        args.retries,
+    )
+    .await?;
+
+    let instructions = bpf_loader_upgradeable::deploy_with_max_program_len(
+        &payer.pubkey(),
+        &program_id,
+        &buffer,
+        &authority.pubkey(),
+        program_lamports,
+        max_data_len,
+    )?;
+    let signature = send_instructions(
+        &client,
+        &instructions,
+        &payer,
+        &[&program_keypair, &authority],
+    )
+    .await?;
+
+    println!("\n{}", style("Successfully deployed program").green());
+    println!(
+        "{} {}",
+        style("Program Id:").bold().cyan(),
+        style(program_id).yellow()
+    );
+    println!(
+        "{} {}",
+        style("ProgramData:").bold().cyan(),
+        style(bpf_loader_upgradeable::get_program_data_address(
+            &program_id
+        ))
+        .yellow()
+    );
+    println!(
+        "{} {}",
+        style("Upgrade authority:").bold().cyan(),
+        style(authority.pubkey()).yellow()
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::config::get_rpc_client;
+use crate::program_manage::show::{fetch_loader_account, LoaderAccount};
+use clap::Parser;
+use console::style;
+use solana_sdk::pubkey::Pubkey;
+
+/// save the deployed program of a program or buffer to a file
+#[derive(Parser, Debug)]
+pub struct DumpArgs {
+    /// Program id or buffer address
+    pub address: String,
+
+    /// File to write the program to
+    pub output: String,
+}
+
+pub async fn handle_dump(args: &DumpArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let address: Pubkey = args.address.parse()?;
+
+    let data = match fetch_loader_account(&client, &address).await? {
+        LoaderAccount::Program { data, .. } => data,
+        LoaderAccount::Buffer { data, .. } => data,
+    };
+    std::fs::write(&args.output, &data)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", args.output, e))?;
+
+    println!(
+        "{} {} bytes to {}",
+        style("Wrote").green(),
+        style(data.len()).yellow(),
+        style(&args.output).yellow()
+    );
+
+    Ok(())
+}
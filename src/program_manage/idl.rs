@@ -0,0 +1,619 @@
+use flate2::read::ZlibDecoder;
+use heck::ToSnakeCase;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::io::Read;
+
+/// An Anchor IDL, both the 0.30 layout and the older one are accepted
+#[derive(Debug, Default, Deserialize)]
+pub struct Idl {
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<Value>,
+    #[serde(default)]
+    pub instructions: Vec<IdlInstruction>,
+    #[serde(default)]
+    pub accounts: Vec<IdlAccount>,
+    #[serde(default)]
+    pub types: Vec<IdlTypeDef>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IdlInstruction {
+    pub name: String,
+    #[serde(default)]
+    pub discriminator: Option<Vec<u8>>,
+    #[serde(default)]
+    pub accounts: Vec<IdlAccountItem>,
+    #[serde(default)]
+    pub args: Vec<IdlField>,
+}
+
+/// an instruction account, or a group of them from a nested `Accounts` struct
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum IdlAccountItem {
+    Composite {
+        name: String,
+        accounts: Vec<IdlAccountItem>,
+    },
+    Single(IdlInstructionAccount),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IdlInstructionAccount {
+    pub name: String,
+    #[serde(default, alias = "isMut")]
+    pub writable: bool,
+    #[serde(default, alias = "isSigner")]
+    pub signer: bool,
+    #[serde(default, alias = "isOptional")]
+    pub optional: bool,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub pda: Option<IdlPda>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IdlPda {
+    pub seeds: Vec<IdlSeed>,
+    #[serde(default)]
+    pub program: Option<IdlSeed>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlSeed {
+    /// bytes in the 0.30 layout, a string in the older one
+    Const {
+        value: Value,
+    },
+    Arg {
+        path: String,
+    },
+    Account {
+        path: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IdlAccount {
+    pub name: String,
+    #[serde(default)]
+    pub discriminator: Option<Vec<u8>>,
+    /// only the older layout declares the type here, 0.30 keeps it in `types`
+    #[serde(default, rename = "type")]
+    pub ty: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IdlTypeDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Value,
+    /// `bytemuck` for zero copy accounts, borsh when absent
+    #[serde(default)]
+    pub serialization: Option<String>,
+}
+
+/// the first 8 bytes of the sha256 of `preimage`, how anchor tags its data
+fn sighash(preimage: &str) -> Vec<u8> {
+    solana_sdk::hash::hash(preimage.as_bytes()).to_bytes()[..8].to_vec()
+}
+
+impl IdlInstruction {
+    pub fn discriminator(&self) -> Vec<u8> {
+        self.discriminator
+            .clone()
+            .unwrap_or_else(|| sighash(&format!("global:{}", self.name.to_snake_case())))
+    }
+}
+
+impl IdlAccount {
+    pub fn discriminator(&self) -> Vec<u8> {
+        self.discriminator
+            .clone()
+            .unwrap_or_else(|| sighash(&format!("account:{}", self.name)))
+    }
+}
+
+impl Idl {
+    /// the program id, `address` in 0.30 and `metadata.address` before
+    pub fn program_id(&self) -> Option<Pubkey> {
+        self.address
+            .as_deref()
+            .or_else(|| self.metadata.as_ref()?.get("address")?.as_str())
+            .and_then(|address| address.parse().ok())
+    }
+
+    /// find an instruction by name, `init_pool` and `initPool` are the same
+    pub fn instruction(&self, name: &str) -> Option<&IdlInstruction> {
+        self.instructions
+            .iter()
+            .find(|instruction| instruction.name.to_snake_case() == name.to_snake_case())
+    }
+
+    pub fn type_def(&self, name: &str) -> Option<&IdlTypeDef> {
+        self.types.iter().find(|def| def.name == name)
+    }
+
+    /// find an account type by name, `user_stats` and `UserStats` are the same
+    pub fn account(&self, name: &str) -> Option<&IdlAccount> {
+        self.accounts
+            .iter()
+            .find(|account| account.name.to_snake_case() == name.to_snake_case())
+    }
+}
+
+/// decode account data into json, the type is picked by its discriminator
+pub fn decode_account<'a>(idl: &'a Idl, data: &[u8]) -> anyhow::Result<(&'a IdlAccount, Value)> {
+    let account = idl
+        .accounts
+        .iter()
+        .find(|account| data.starts_with(&account.discriminator()))
+        .ok_or_else(|| anyhow::anyhow!("No account type of the IDL matches the discriminator"))?;
+    let ty = match &account.ty {
+        Some(ty) => ty,
+        None => {
+            let def = idl
+                .type_def(&account.name)
+                .ok_or_else(|| anyhow::anyhow!("Type {} is not in the IDL", account.name))?;
+            if def
+                .serialization
+                .as_deref()
+                .is_some_and(|serialization| serialization != "borsh")
+            {
+                anyhow::bail!(
+                    "{} is a zero copy account, only borsh accounts can be decoded",
+                    account.name
+                );
+            }
+            &def.ty
+        }
+    };
+    // accounts are often allocated larger than their content, the rest is ignored
+    let mut rest = &data[account.discriminator().len()..];
+    let value = decode_type_def(idl, ty, &mut rest)?;
+    Ok((account, value))
+}
+
+/// read an IDL file
+pub fn read_idl(path: &str) -> anyhow::Result<Idl> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?;
+    serde_json::from_str(&content).map_err(|e| anyhow::anyhow!("Invalid IDL {}: {}", path, e))
+}
+
+/// fetch the IDL anchor publishes on chain for `program_id`
+pub async fn fetch_idl(client: &RpcClient, program_id: &Pubkey) -> anyhow::Result<Idl> {
+    let base = Pubkey::find_program_address(&[], program_id).0;
+    let address = Pubkey::create_with_seed(&base, "anchor:idl", program_id)?;
+    let account = client
+        .get_account_with_commitment(&address, client.commitment())
+        .await?
+        .value
+        .ok_or_else(|| anyhow::anyhow!("Program {} has no IDL on chain", program_id))?;
+
+    // discriminator and authority, then the length prefixed zlib compressed json
+    let data = account
+        .data
+        .get(40..)
+        .ok_or_else(|| anyhow::anyhow!("IDL account {} is too short", address))?;
+    let len = data
+        .get(..4)
+        .ok_or_else(|| anyhow::anyhow!("IDL account {} is too short", address))?;
+    let len = u32::from_le_bytes(len.try_into()?) as usize;
+    let compressed = data
+        .get(4..4 + len)
+        .ok_or_else(|| anyhow::anyhow!("IDL account {} is truncated", address))?;
+    let mut json = vec![];
+    ZlibDecoder::new(compressed).read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// the IDL from a file, or from chain when no file is given
+pub async fn load_idl(
+    client: &RpcClient,
+    path: Option<&str>,
+    program_id: Option<&Pubkey>,
+) -> anyhow::Result<Idl> {
+    match (path, program_id) {
+        (Some(path), _) => read_idl(path),
+        (None, Some(program_id)) => fetch_idl(client, program_id).await,
+        (None, None) => anyhow::bail!("Pass --idl or --program-id to fetch the IDL from chain"),
+    }
+}
+
+/// a field of a json object, matching `poolId` against `pool_id` too
+pub fn lookup<'a>(object: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    object.get(name).or_else(|| {
+        object
+            .iter()
+            .find(|(key, _)| key.to_snake_case() == name.to_snake_case())
+            .map(|(_, value)| value)
+    })
+}
+
+/// the type definition a `defined` type points at, the name is a plain string
+/// before 0.30 and an object with generics after
+pub fn defined<'a>(idl: &'a Idl, ty: &Value) -> Option<&'a IdlTypeDef> {
+    let defined = ty.get("defined")?;
+    let name = defined.as_str().or_else(|| defined.get("name")?.as_str())?;
+    idl.type_def(name)
+}
+
+fn integer<T: std::str::FromStr>(value: &Value) -> anyhow::Result<T> {
+    // large integers are best passed as strings, json numbers lose precision
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        _ => anyhow::bail!("Expected an integer, got {}", value),
+    };
+    text.parse()
+        .map_err(|_| anyhow::anyhow!("Invalid integer {}", value))
+}
+
+/// borsh-encode `value` as the IDL type `ty`
+pub fn encode(idl: &Idl, ty: &Value, value: &Value, out: &mut Vec<u8>) -> anyhow::Result<()> {
+    if let Some(name) = ty.as_str() {
+        match name {
+            "bool" => out.push(
+                value
+                    .as_bool()
+                    .ok_or_else(|| anyhow::anyhow!("Expected a bool, got {}", value))?
+                    as u8,
+            ),
+            "u8" => out.push(integer(value)?),
+            "i8" => out.extend(integer::<i8>(value)?.to_le_bytes()),
+            "u16" => out.extend(integer::<u16>(value)?.to_le_bytes()),
+            "i16" => out.extend(integer::<i16>(value)?.to_le_bytes()),
+            "u32" => out.extend(integer::<u32>(value)?.to_le_bytes()),
+            "i32" => out.extend(integer::<i32>(value)?.to_le_bytes()),
+            "u64" => out.extend(integer::<u64>(value)?.to_le_bytes()),
+            "i64" => out.extend(integer::<i64>(value)?.to_le_bytes()),
+            "u128" => out.extend(integer::<u128>(value)?.to_le_bytes()),
+            "i128" => out.extend(integer::<i128>(value)?.to_le_bytes()),
+            "f32" => out.extend((integer::<f64>(value)? as f32).to_le_bytes()),
+            "f64" => out.extend(integer::<f64>(value)?.to_le_bytes()),
+            "string" => {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("Expected a string, got {}", value))?;
+                out.extend((text.len() as u32).to_le_bytes());
+                out.extend(text.as_bytes());
+            }
+            "bytes" => {
+                let bytes = serde_json::from_value::<Vec<u8>>(value.clone())
+                    .map_err(|_| anyhow::anyhow!("Expected a byte array, got {}", value))?;
+                out.extend((bytes.len() as u32).to_le_bytes());
+                out.extend(bytes);
+            }
+            "pubkey" | "publicKey" => {
+                let pubkey: Pubkey = value
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("Expected an address, got {}", value))?
+                    .parse()?;
+                out.extend(pubkey.to_bytes());
+            }
+            _ => anyhow::bail!("Unsupported IDL type {}", name),
+        }
+        return Ok(());
+    }
+
+    if let Some(inner) = ty.get("vec") {
+        let items = value
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Expected an array, got {}", value))?;
+        out.extend((items.len() as u32).to_le_bytes());
+        for item in items {
+            encode(idl, inner, item, out)?;
+        }
+    } else if let Some(inner) = ty.get("option") {
+        if value.is_null() {
+            out.push(0);
+        } else {
+            out.push(1);
+            encode(idl, inner, value, out)?;
+        }
+    } else if let Some(array) = ty.get("array") {
+        let (inner, len) = (&array[0], array[1].as_u64());
+        let items = value
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Expected an array, got {}", value))?;
+        if len != Some(items.len() as u64) {
+            anyhow::bail!("Expected {} items, got {}", array[1], items.len());
+        }
+        for item in items {
+            encode(idl, inner, item, out)?;
+        }
+    } else if ty.get("defined").is_some() {
+        let def =
+            defined(idl, ty).ok_or_else(|| anyhow::anyhow!("Type {} is not in the IDL", ty))?;
+        encode_type_def(idl, &def.ty, value, out)
+            .map_err(|e| anyhow::anyhow!("{}: {}", def.name, e))?;
+    } else {
+        anyhow::bail!("Unsupported IDL type {}", ty);
+    }
+    Ok(())
+}
+
+fn encode_type_def(idl: &Idl, def: &Value, value: &Value, out: &mut Vec<u8>) -> anyhow::Result<()> {
+    match def["kind"].as_str() {
+        Some("struct") => encode_fields(idl, def.get("fields"), value, out),
+        Some("enum") => {
+            let variants = def["variants"]
+                .as_array()
+                .ok_or_else(|| anyhow::anyhow!("Enum without variants"))?;
+            // a unit variant is a bare string, others are {"Variant": fields}
+            let (name, fields) = match value {
+                Value::String(name) => (name.as_str(), &Value::Null),
+                Value::Object(object) if object.len() == 1 => {
+                    let (name, fields) = object.iter().next().unwrap();
+                    (name.as_str(), fields)
+                }
+                _ => anyhow::bail!("Expected a variant name or {{\"Variant\": ...}}"),
+            };
+            let index = variants
+                .iter()
+                .position(|variant| {
+                    variant["name"]
+                        .as_str()
+                        .is_some_and(|variant| variant.to_snake_case() == name.to_snake_case())
+                })
+                .ok_or_else(|| anyhow::anyhow!("Unknown variant {}", name))?;
+            out.push(index as u8);
+            encode_fields(idl, variants[index].get("fields"), fields, out)
+        }
+        Some("type") => encode(idl, &def["alias"], value, out),
+        _ => anyhow::bail!("Unsupported type definition {}", def),
+    }
+}
+
+/// named fields take a json object, tuple fields an array
+fn encode_fields(
+    idl: &Idl,
+    fields: Option<&Value>,
+    value: &Value,
+    out: &mut Vec<u8>,
+) -> anyhow::Result<()> {
+    let Some(fields) = fields.and_then(Value::as_array) else {
+        return Ok(());
+    };
+    for (i, field) in fields.iter().enumerate() {
+        match field.get("name").and_then(Value::as_str) {
+            Some(name) => {
+                let field_value = value
+                    .as_object()
+                    .and_then(|object| lookup(object, name))
+                    .ok_or_else(|| anyhow::anyhow!("Missing field {}", name))?;
+                encode(idl, &field["type"], field_value, out)
+                    .map_err(|e| anyhow::anyhow!("{}: {}", name, e))?;
+            }
+            None => {
+                let field_value = value
+                    .get(i)
+                    .ok_or_else(|| anyhow::anyhow!("Missing tuple field {}", i))?;
+                encode(idl, field, field_value, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn take<'a>(data: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+    if data.len() < len {
+        anyhow::bail!("Unexpected end of data");
+    }
+    let (head, rest) = data.split_at(len);
+    *data = rest;
+    Ok(head)
+}
+
+fn take_array<const N: usize>(data: &mut &[u8]) -> anyhow::Result<[u8; N]> {
+    Ok(take(data, N)?.try_into()?)
+}
+
+fn take_len(data: &mut &[u8]) -> anyhow::Result<usize> {
+    Ok(u32::from_le_bytes(take_array(data)?) as usize)
+}
+
+/// borsh-decode a value of the IDL type `ty` into json, 128 bit integers
+/// become strings since json numbers can not hold them
+pub fn decode(idl: &Idl, ty: &Value, data: &mut &[u8]) -> anyhow::Result<Value> {
+    if let Some(name) = ty.as_str() {
+        return Ok(match name {
+            "bool" => Value::Bool(take(data, 1)?[0] != 0),
+            "u8" => take(data, 1)?[0].into(),
+            "i8" => i8::from_le_bytes(take_array(data)?).into(),
+            "u16" => u16::from_le_bytes(take_array(data)?).into(),
+            "i16" => i16::from_le_bytes(take_array(data)?).into(),
+            "u32" => u32::from_le_bytes(take_array(data)?).into(),
+            "i32" => i32::from_le_bytes(take_array(data)?).into(),
+            "u64" => u64::from_le_bytes(take_array(data)?).into(),
+            "i64" => i64::from_le_bytes(take_array(data)?).into(),
+            "u128" => u128::from_le_bytes(take_array(data)?).to_string().into(),
+            "i128" => i128::from_le_bytes(take_array(data)?).to_string().into(),
+            "f32" => f32::from_le_bytes(take_array(data)?).into(),
+            "f64" => f64::from_le_bytes(take_array(data)?).into(),
+            "string" => {
+                let len = take_len(data)?;
+                String::from_utf8(take(data, len)?.to_vec())?.into()
+            }
+            "bytes" => {
+                let len = take_len(data)?;
+                take(data, len)?.to_vec().into()
+            }
+            "pubkey" | "publicKey" => Pubkey::new_from_array(take_array(data)?).to_string().into(),
+            _ => anyhow::bail!("Unsupported IDL type {}", name),
+        });
+    }
+
+    if let Some(inner) = ty.get("vec") {
+        let len = take_len(data)?;
+        // every item takes at least a byte, a bogus length fails here instead
+        // of allocating
+        if len > data.len() {
+            anyhow::bail!("Vector length {} exceeds the data", len);
+        }
+        (0..len)
+            .map(|_| decode(idl, inner, data))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map(Value::Array)
+    } else if let Some(inner) = ty.get("option") {
+        match take(data, 1)?[0] {
+            0 => Ok(Value::Null),
+            _ => decode(idl, inner, data),
+        }
+    } else if let Some(array) = ty.get("array") {
+        let len = array[1]
+            .as_u64()
+            .ok_or_else(|| anyhow::anyhow!("Unsupported array length {}", array[1]))?;
+        (0..len)
+            .map(|_| decode(idl, &array[0], data))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map(Value::Array)
+    } else if ty.get("defined").is_some() {
+        let def =
+            defined(idl, ty).ok_or_else(|| anyhow::anyhow!("Type {} is not in the IDL", ty))?;
+        decode_type_def(idl, &def.ty, data).map_err(|e| anyhow::anyhow!("{}: {}", def.name, e))
+    } else {
+        anyhow::bail!("Unsupported IDL type {}", ty)
+    }
+}
+
+fn decode_type_def(idl: &Idl, def: &Value, data: &mut &[u8]) -> anyhow::Result<Value> {
+    match def["kind"].as_str() {
+        Some("struct") => decode_fields(idl, def.get("fields"), data),
+        Some("enum") => {
+            let index = take(data, 1)?[0] as usize;
+            let variant = def["variants"]
+                .get(index)
+                .ok_or_else(|| anyhow::anyhow!("Unknown variant index {}", index))?;
+            let name = variant["name"].as_str().unwrap_or_default().to_string();
+            match variant.get("fields") {
+                Some(fields) => {
+                    let fields = decode_fields(idl, Some(fields), data)?;
+                    Ok(serde_json::json!({ name: fields }))
+                }
+                None => Ok(Value::String(name)),
+            }
+        }
+        Some("type") => decode(idl, &def["alias"], data),
+        _ => anyhow::bail!("Unsupported type definition {}", def),
+    }
+}
+
+/// named fields become a json object, tuple fields an array
+fn decode_fields(idl: &Idl, fields: Option<&Value>, data: &mut &[u8]) -> anyhow::Result<Value> {
+    let Some(fields) = fields.and_then(Value::as_array) else {
+        return Ok(Value::Null);
+    };
+    if fields.iter().all(|field| field.get("name").is_some()) {
+        let mut object = Map::new();
+        for field in fields {
+            let name = field["name"].as_str().unwrap_or_default();
+            let value = decode(idl, &field["type"], data)
+                .map_err(|e| anyhow::anyhow!("{}: {}", name, e))?;
+            object.insert(name.to_string(), value);
+        }
+        Ok(Value::Object(object))
+    } else {
+        fields
+            .iter()
+            .map(|field| decode(idl, field, data))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map(Value::Array)
+    }
+}
+
+#[test]
+fn test_idl_encode_decode() {
+    use borsh::BorshSerialize;
+
+    #[derive(BorshSerialize)]
+    enum Side {
+        Bid,
+        Ask { limit: u64 },
+    }
+    #[derive(BorshSerialize)]
+    struct Order {
+        owner: Pubkey,
+        side: Side,
+        tags: Vec<String>,
+        expiry: Option<i64>,
+        salt: [u8; 2],
+    }
+
+    let idl: Idl = serde_json::from_value(serde_json::json!({
+        "instructions": [],
+        "types": [
+            {"name": "Side", "type": {"kind": "enum", "variants": [
+                {"name": "Bid"},
+                {"name": "Ask", "fields": [{"name": "limit", "type": "u64"}]}
+            ]}},
+            {"name": "Order", "type": {"kind": "struct", "fields": [
+                {"name": "owner", "type": "pubkey"},
+                {"name": "side", "type": {"defined": {"name": "Side"}}},
+                {"name": "tags", "type": {"vec": "string"}},
+                {"name": "expiry", "type": {"option": "i64"}},
+                {"name": "salt", "type": {"array": ["u8", 2]}}
+            ]}}
+        ]
+    }))
+    .unwrap();
+
+    let owner = Pubkey::new_unique();
+    let value = serde_json::json!({
+        "owner": owner.to_string(),
+        "side": {"Ask": {"limit": "18446744073709551615"}},
+        "tags": ["a", "bc"],
+        "expiry": -5,
+        "salt": [7, 9]
+    });
+    let mut encoded = vec![];
+    encode(
+        &idl,
+        &serde_json::json!({"defined": "Order"}),
+        &value,
+        &mut encoded,
+    )
+    .unwrap();
+
+    let order = Order {
+        owner,
+        side: Side::Ask { limit: u64::MAX },
+        tags: vec!["a".to_string(), "bc".to_string()],
+        expiry: Some(-5),
+        salt: [7, 9],
+    };
+    assert_eq!(encoded, borsh::to_vec(&order).unwrap());
+
+    let mut encoded = vec![];
+    let side = serde_json::json!({"defined": "Side"});
+    encode(&idl, &side, &serde_json::json!("bid"), &mut encoded).unwrap();
+    assert_eq!(encoded, borsh::to_vec(&Side::Bid).unwrap());
+    assert_eq!(decode(&idl, &side, &mut encoded.as_slice()).unwrap(), "Bid");
+
+    // decoding gives back the same json, with 64 bit integers as numbers
+    let mut data = borsh::to_vec(&order).unwrap();
+    data.extend([0; 16]);
+    let decoded = decode(
+        &idl,
+        &serde_json::json!({"defined": "Order"}),
+        &mut data.as_slice(),
+    );
+    let mut expected = value;
+    expected["side"]["Ask"]["limit"] = u64::MAX.into();
+    assert_eq!(decoded.unwrap(), expected);
+}
@@ -0,0 +1,101 @@
+use crate::config::{get_rpc_client, is_mainnet};
+use crate::program_manage::deploy::send_instructions;
+use crate::program_manage::show::{fetch_loader_account, LoaderAccount};
+use crate::utils::{confirm, confirm_typed, read_keypair_or_default};
+use clap::Parser;
+use console::style;
+use solana_sdk::bpf_loader_upgradeable;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// change or remove the upgrade authority of a program or buffer
+#[derive(Parser, Debug)]
+pub struct SetUpgradeAuthorityArgs {
+    /// Program id or buffer address
+    pub address: String,
+
+    /// New upgrade authority
+    #[clap(long, required_unless_present = "final_", conflicts_with = "final_")]
+    pub new_authority: Option<String>,
+
+    /// Remove the upgrade authority, the program can never be upgraded again
+    #[clap(long = "final")]
+    pub final_: bool,
+
+    /// Payer keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    /// Current authority keypair path (optional, the payer if not provided)
+    #[clap(long)]
+    pub upgrade_authority: Option<String>,
+}
+
+pub async fn handle_set_upgrade_authority(args: &SetUpgradeAuthorityArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let address: Pubkey = args.address.parse()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let authority = match &args.upgrade_authority {
+        Some(path) => read_keypair_or_default(Some(path))?,
+        None => payer.insecure_clone(),
+    };
+    let new_authority = match &args.new_authority {
+        Some(new_authority) => Some(new_authority.parse::<Pubkey>()?),
+        None => None,
+    };
+
+    let (is_program, current) = match fetch_loader_account(&client, &address).await? {
+        LoaderAccount::Program { authority, .. } => (true, authority),
+        LoaderAccount::Buffer { authority, .. } => (false, authority),
+    };
+    if current != Some(authority.pubkey()) {
+        anyhow::bail!("{} is not the authority of {}", authority.pubkey(), address);
+    }
+    let instruction = match (is_program, new_authority) {
+        (true, new_authority) => bpf_loader_upgradeable::set_upgrade_authority(
+            &address,
+            &authority.pubkey(),
+            new_authority.as_ref(),
+        ),
+        (false, Some(new_authority)) => bpf_loader_upgradeable::set_buffer_authority(
+            &address,
+            &authority.pubkey(),
+            &new_authority,
+        ),
+        (false, None) => anyhow::bail!("The authority of a buffer can not be removed"),
+    };
+
+    if new_authority.is_none() {
+        if !confirm_typed(
+            "Removing the upgrade authority is permanent.",
+            &address.to_string(),
+        )? {
+            println!("{}", style("Aborted").yellow());
+            return Ok(());
+        }
+    } else if is_mainnet(&client).await?
+        && !confirm(&format!(
+            "Move the authority of {} to {} on mainnet?",
+            address,
+            args.new_authority.as_deref().unwrap_or_default()
+        ))?
+    {
+        println!("{}", style("Aborted").yellow());
+        return Ok(());
+    }
+
+    let signature = send_instructions(&client, &[instruction], &payer, &[&authority]).await?;
+
+    println!("\n{}", style("Successfully updated the authority").green());
+    println!(
+        "{} {}",
+        style("New authority:").bold().cyan(),
+        match new_authority {
+            Some(new_authority) => style(new_authority.to_string()).yellow(),
+            None => style("none (immutable)".to_string()).red(),
+        }
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::extensions::display_authority;
+use clap::Parser;
+use console::style;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+
+/// show the state of a program or buffer
+#[derive(Parser, Debug)]
+pub struct ShowArgs {
+    /// Program id or buffer address
+    pub address: String,
+}
+
+/// an account of the upgradeable loader, with the program bytes it holds
+#[derive(Debug)]
+pub enum LoaderAccount {
+    Program {
+        program_data: Pubkey,
+        last_deploy_slot: u64,
+        authority: Option<Pubkey>,
+        data: Vec<u8>,
+        /// program and ProgramData account together
+        lamports: u64,
+        /// the part held by the ProgramData account, returned on close
+        program_data_lamports: u64,
+    },
+    Buffer {
+        authority: Option<Pubkey>,
+        data: Vec<u8>,
+        lamports: u64,
+    },
+}
+
+/// fetch a program, following it to its ProgramData account, or a buffer
+pub async fn fetch_loader_account(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> anyhow::Result<LoaderAccount> {
+    let account = client
+        .get_account_with_commitment(address, client.commitment())
+        .await?
+        .value
+        .ok_or_else(|| anyhow::anyhow!("Account {} not found", address))?;
+    if account.owner != bpf_loader_upgradeable::id() {
+        anyhow::bail!(
+            "{} is not owned by the upgradeable loader ({})",
+            address,
+            account.owner
+        );
+    }
+
+    match bincode::deserialize::<UpgradeableLoaderState>(&account.data)? {
+        UpgradeableLoaderState::Program {
+            programdata_address,
+        } => {
+            let program_data = client
+                .get_account_with_commitment(&programdata_address, client.commitment())
+                .await?
+                .value
+                .ok_or_else(|| anyhow::anyhow!("Program {} has been closed", address))?;
+            let UpgradeableLoaderState::ProgramData {
+                slot,
+                upgrade_authority_address,
+            } = bincode::deserialize(&program_data.data)?
+            else {
+                anyhow::bail!("{} is not a ProgramData account", programdata_address);
+            };
+            Ok(LoaderAccount::Program {
+                program_data: programdata_address,
+                last_deploy_slot: slot,
+                authority: upgrade_authority_address,
+                data: program_data
+                    .data
+                    .get(UpgradeableLoaderState::size_of_programdata_metadata()..)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("ProgramData account {} is too short", programdata_address)
+                    })?
+                    .to_vec(),
+                lamports: account.lamports + program_data.lamports,
+                program_data_lamports: program_data.lamports,
+            })
+        }
+        UpgradeableLoaderState::Buffer { authority_address } => Ok(LoaderAccount::Buffer {
+            authority: authority_address,
+            data: account
+                .data
+                .get(UpgradeableLoaderState::size_of_buffer_metadata()..)
+                .ok_or_else(|| anyhow::anyhow!("Buffer account {} is too short", address))?
+                .to_vec(),
+            lamports: account.lamports,
+        }),
+        UpgradeableLoaderState::ProgramData { .. } => {
+            anyhow::bail!("{} is a ProgramData account, pass the program id", address)
+        }
+        UpgradeableLoaderState::Uninitialized => {
+            anyhow::bail!("{} is not initialized", address)
+        }
+    }
+}
+
+pub async fn handle_show(args: &ShowArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let address: Pubkey = args.address.parse()?;
+
+    match fetch_loader_account(&client, &address).await? {
+        LoaderAccount::Program {
+            program_data,
+            last_deploy_slot,
+            authority,
+            data,
+            lamports,
+            ..
+        } => {
+            println!(
+                "{} {}",
+                style("Program Id:").bold().cyan(),
+                style(address).yellow()
+            );
+            println!(
+                "{} {}",
+                style("ProgramData:").bold().cyan(),
+                style(program_data).yellow()
+            );
+            println!(
+                "{} {}",
+                style("Upgrade authority:").bold().cyan(),
+                match authority {
+                    Some(authority) => style(authority.to_string()).yellow(),
+                    None => style("none (immutable)".to_string()).red(),
+                }
+            );
+            println!(
+                "{} {}",
+                style("Last deployed slot:").bold().cyan(),
+                style(last_deploy_slot).yellow()
+            );
+            println!(
+                "{} {} bytes",
+                style("Data length:").bold().cyan(),
+                style(data.len()).yellow()
+            );
+            println!(
+                "{} {} SOL",
+                style("Balance:").bold().cyan(),
+                style(lamports_to_sol(lamports)).green()
+            );
+        }
+        LoaderAccount::Buffer {
+            authority,
+            data,
+            lamports,
+        } => {
+            println!(
+                "{} {}",
+                style("Buffer:").bold().cyan(),
+                style(address).yellow()
+            );
+            println!(
+                "{} {}",
+                style("Authority:").bold().cyan(),
+                style(display_authority(authority)).yellow()
+            );
+            println!(
+                "{} {} bytes",
+                style("Data length:").bold().cyan(),
+                style(data.len()).yellow()
+            );
+            println!(
+                "{} {} SOL",
+                style("Balance:").bold().cyan(),
+                style(lamports_to_sol(lamports)).green()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::config::get_rpc_client;
+use crate::program_manage::deploy::{
+    create_or_resume_buffer, read_program, send_instructions, write_buffer,
+};
+use crate::program_manage::show::{fetch_loader_account, LoaderAccount};
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use solana_sdk::bpf_loader_upgradeable;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// upgrade a deployed program with a new build
+#[derive(Parser, Debug)]
+pub struct UpgradeArgs {
+    /// Path of the compiled program (.so)
+    pub program: String,
+
+    /// Program id to upgrade
+    pub program_id: String,
+
+    /// Payer keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    /// Upgrade authority keypair path (optional, the payer if not provided)
+    #[clap(long)]
+    pub upgrade_authority: Option<String>,
+
+    /// Resume from this buffer instead of creating a new one
+    #[clap(long)]
+    pub buffer: Option<String>,
+
+    /// Number of buffer writes in flight at the same time
+    #[clap(long, default_value = "8")]
+    pub concurrency: usize,
+
+    /// Rounds of retries for failed buffer writes
+    #[clap(long, default_value = "5")]
+    pub retries: usize,
+}
+
+pub async fn handle_upgrade(args: &UpgradeArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let program = read_program(&args.program)?;
+    let program_id: Pubkey = args.program_id.parse()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let authority = match &args.upgrade_authority {
+        Some(path) => read_keypair_or_default(Some(path))?,
+        None => payer.insecure_clone(),
+    };
+
+    let LoaderAccount::Program {
+        authority: current,
+        data,
+        ..
+    } = fetch_loader_account(&client, &program_id).await?
+    else {
+        anyhow::bail!("{} is a buffer, not a program", program_id);
+    };
+    match current {
+        None => anyhow::bail!("Program {} is immutable", program_id),
+        Some(current) if current != authority.pubkey() => anyhow::bail!(
+            "{} is not the upgrade authority of {} ({})",
+            authority.pubkey(),
+            program_id,
+            current
+        ),
+        Some(_) => {}
+    }
+
+    // grow the program data first when the new build does not fit
+    if program.len() > data.len() {
+        let additional = program.len() - data.len();
+        let extend = bpf_loader_upgradeable::extend_program(
+            &program_id,
+            Some(&payer.pubkey()),
+            additional as u32,
+        );
+        send_instructions(&client, &[extend], &payer, &[]).await?;
+        println!(
+            "{} by {} bytes",
+            style("Extended program data").bold().cyan(),
+            style(additional).yellow()
+        );
+    }
+
+    let buffer = create_or_resume_buffer(
+        &client,
+        &payer,
+        &authority,
+        &program,
+        args.buffer.as_deref(),
+    )
+    .await?;
+    write_buffer(
+        &client,
+        &payer,
+        &authority,
+        &buffer,
+        &program,
+        args.concurrency,
+        args.retries,
+    )
+    .await?;
+
+    // the buffer rent is refunded to the payer
+    let upgrade =
+        bpf_loader_upgradeable::upgrade(&program_id, &buffer, &authority.pubkey(), &payer.pubkey());
+    let signature = send_instructions(&client, &[upgrade], &payer, &[&authority]).await?;
+
+    println!("\n{}", style("Successfully upgraded program").green());
+    println!(
+        "{} {}",
+        style("Program Id:").bold().cyan(),
+        style(program_id).yellow()
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
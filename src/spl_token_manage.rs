@@ -1,8 +1,23 @@
 use clap::Parser;
 
+pub mod authorize;
+pub mod burn;
+pub mod cleanup;
 pub mod create_token;
+pub mod delegate;
+pub mod extensions;
+pub mod freeze;
 pub mod get_balance;
+pub mod holders;
+pub mod metadata;
+pub mod mint_info;
+pub mod mint_to;
+pub mod portfolio;
+pub mod show_metadata;
+pub mod token_program;
 pub mod transfer_token;
+pub mod update_metadata;
+pub mod wrap;
 
 #[derive(Parser, Debug)]
 pub enum SplTokenMange {
@@ -12,6 +27,39 @@ pub enum SplTokenMange {
     GetBalance(get_balance::GetBalanceArgs),
     /// transfer spl token
     TransferToken(transfer_token::TransferTokenArgs),
+    /// update spl token metadata
+    UpdateMetadata(update_metadata::UpdateMetadataArgs),
+    /// show spl token metadata
+    ShowMetadata(show_metadata::ShowMetadataArgs),
+    /// mint spl token to a wallet
+    MintTo(mint_to::MintToArgs),
+    /// burn spl token
+    Burn(burn::BurnArgs),
+    /// change or revoke a mint or token account authority
+    Authorize(authorize::AuthorizeArgs),
+    /// freeze a token account
+    Freeze(freeze::FreezeArgs),
+    /// thaw a frozen token account
+    Thaw(freeze::FreezeArgs),
+    /// approve a delegate to spend tokens
+    Approve(delegate::ApproveArgs),
+    /// revoke the delegate of a token account
+    Revoke(delegate::RevokeArgs),
+    /// list every token holding of a wallet
+    Portfolio(portfolio::PortfolioArgs),
+    /// close empty token accounts and reclaim their rent
+    Cleanup(cleanup::CleanupArgs),
+    /// wrap SOL into wSOL
+    Wrap(wrap::WrapArgs),
+    /// unwrap wSOL back into SOL
+    Unwrap(wrap::UnwrapArgs),
+    /// snapshot every holder of a mint
+    ///
+    /// The rpc node returns all token accounts of the mint in one response, so the
+    /// whole snapshot is held in memory before the first row is written.
+    Holders(holders::HoldersArgs),
+    /// show the supply, authorities, extensions and top holders of a mint
+    MintInfo(mint_info::MintInfoArgs),
 }
 
 pub async fn handle_spl_token_manage(spl_token_manage: &SplTokenMange) -> anyhow::Result<()> {
@@ -21,5 +69,20 @@ pub async fn handle_spl_token_manage(spl_token_manage: &SplTokenMange) -> anyhow
         }
         SplTokenMange::GetBalance(args) => get_balance::handle_get_balance(args).await,
         SplTokenMange::TransferToken(args) => transfer_token::handle_transfer_token(args).await,
+        SplTokenMange::UpdateMetadata(args) => update_metadata::handle_update_metadata(args).await,
+        SplTokenMange::ShowMetadata(args) => show_metadata::handle_show_metadata(args).await,
+        SplTokenMange::MintTo(args) => mint_to::handle_mint_to(args).await,
+        SplTokenMange::Burn(args) => burn::handle_burn(args).await,
+        SplTokenMange::Authorize(args) => authorize::handle_authorize(args).await,
+        SplTokenMange::Freeze(args) => freeze::handle_freeze(args, true).await,
+        SplTokenMange::Thaw(args) => freeze::handle_freeze(args, false).await,
+        SplTokenMange::Approve(args) => delegate::handle_approve(args).await,
+        SplTokenMange::Revoke(args) => delegate::handle_revoke(args).await,
+        SplTokenMange::Portfolio(args) => portfolio::handle_portfolio(args).await,
+        SplTokenMange::Cleanup(args) => cleanup::handle_cleanup(args).await,
+        SplTokenMange::Wrap(args) => wrap::handle_wrap(args).await,
+        SplTokenMange::Unwrap(args) => wrap::handle_unwrap(args).await,
+        SplTokenMange::Holders(args) => holders::handle_holders(args).await,
+        SplTokenMange::MintInfo(args) => mint_info::handle_mint_info(args).await,
     }
 }
@@ -0,0 +1,216 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::token_program::MultisigArgs;
+use crate::utils::{confirm_typed, read_keypair_or_default};
+use clap::{Parser, ValueEnum};
+use console::style;
+use solana_sdk::program_option::COption;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::Transaction;
+use spl_token_2022::extension::mint_close_authority::MintCloseAuthority;
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use spl_token_2022::instruction::AuthorityType;
+use spl_token_2022::state::{Account, Mint};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthorityKind {
+    /// Mint authority of a mint
+    Mint,
+    /// Freeze authority of a mint
+    Freeze,
+    /// Owner of a token account
+    Owner,
+    /// Close authority of a token account or a token-2022 mint
+    Close,
+}
+
+/// change or revoke an authority of a mint or token account
+#[derive(Parser, Debug)]
+pub struct AuthorizeArgs {
+    /// Mint or token account address
+    #[clap(short, long)]
+    pub account: String,
+
+    /// Authority to change
+    #[clap(short = 't', long, value_enum)]
+    pub authority_type: AuthorityKind,
+
+    /// New authority address
+    #[clap(short, long, required_unless_present = "revoke")]
+    pub new_authority: Option<String>,
+
+    /// Permanently remove the authority, this can not be undone
+    #[clap(long, conflicts_with = "new_authority")]
+    pub revoke: bool,
+
+    /// Current authority keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    #[clap(flatten)]
+    pub multisig: MultisigArgs,
+}
+
+fn display_authority(authority: COption<Pubkey>) -> String {
+    match authority {
+        COption::Some(authority) => authority.to_string(),
+        COption::None => "none".to_string(),
+    }
+}
+
+fn print_authority(label: &str, authority: COption<Pubkey>) {
+    println!(
+        "{} {}",
+        style(format!("{}:", label)).bold().cyan(),
+        style(display_authority(authority)).yellow()
+    );
+}
+
+/// print every authority of the account and return the requested one
+fn current_authority(
+    data: &[u8],
+    authority_kind: AuthorityKind,
+) -> anyhow::Result<(AuthorityType, COption<Pubkey>)> {
+    // token accounts are checked first, a mint never decodes as an account
+    if let Ok(account) = StateWithExtensions::<Account>::unpack(data) {
+        let account = account.base;
+        // without a close authority the owner may close the account
+        let close_authority = account.close_authority.or(COption::Some(account.owner));
+        println!("{}", style("Token account authorities").bold().underlined());
+        print_authority("Mint", COption::Some(account.mint));
+        print_authority("Owner", COption::Some(account.owner));
+        print_authority("Close authority", close_authority);
+        print_authority("Delegate", account.delegate);
+
+        return match authority_kind {
+            AuthorityKind::Owner => Ok((AuthorityType::AccountOwner, COption::Some(account.owner))),
+            AuthorityKind::Close => Ok((AuthorityType::CloseAccount, close_authority)),
+            _ => anyhow::bail!("{:?} authority only exists on mints", authority_kind),
+        };
+    }
+
+    let mint = StateWithExtensions::<Mint>::unpack(data)
+        .map_err(|_| anyhow::anyhow!("Account is neither a mint nor a token account"))?;
+    let close_authority = mint
+        .get_extension::<MintCloseAuthority>()
+        .ok()
+        .and_then(|close| Option::<Pubkey>::from(close.close_authority))
+        .map(COption::Some)
+        .unwrap_or(COption::None);
+
+    println!("{}", style("Mint authorities").bold().underlined());
+    print_authority("Mint authority", mint.base.mint_authority);
+    print_authority("Freeze authority", mint.base.freeze_authority);
+    print_authority("Close authority", close_authority);
+
+    match authority_kind {
+        AuthorityKind::Mint => Ok((AuthorityType::MintTokens, mint.base.mint_authority)),
+        AuthorityKind::Freeze => Ok((AuthorityType::FreezeAccount, mint.base.freeze_authority)),
+        AuthorityKind::Close => Ok((AuthorityType::CloseMint, close_authority)),
+        AuthorityKind::Owner => anyhow::bail!("Mints have no owner authority"),
+    }
+}
+
+pub async fn handle_authorize(args: &AuthorizeArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let (authority, multisig_signers) = args.multisig.resolve(&payer)?;
+
+    let address: Pubkey = args.account.parse()?;
+    let account = client.get_account(&address).await?;
+    if account.owner != spl_token::id() && account.owner != spl_token_2022::id() {
+        anyhow::bail!("{} is not owned by a token program", address);
+    }
+
+    let (authority_type, current) = current_authority(&account.data, args.authority_type)?;
+    if current != COption::Some(authority) {
+        anyhow::bail!(
+            "{} is not the current {:?} authority ({})",
+            authority,
+            args.authority_type,
+            display_authority(current)
+        );
+    }
+
+    let new_authority = match &args.new_authority {
+        Some(new_authority) => Some(new_authority.parse::<Pubkey>()?),
+        None => None,
+    };
+
+    if new_authority.is_none() {
+        if authority_type == AuthorityType::AccountOwner {
+            anyhow::bail!("The owner of a token account can not be revoked");
+        }
+        if !confirm_typed("Revoking an authority is permanent.", &address.to_string())? {
+            println!("{}", style("Aborted").yellow());
+            return Ok(());
+        }
+    }
+
+    let signer_pubkeys = multisig_signers
+        .iter()
+        .map(|signer| signer.pubkey())
+        .collect::<Vec<_>>();
+    let set_authority_ix = spl_token_2022::instruction::set_authority(
+        &account.owner,
+        &address,
+        new_authority.as_ref(),
+        authority_type.clone(),
+        &authority,
+        &signer_pubkeys.iter().collect::<Vec<_>>(),
+    )?;
+
+    let mut signers: Vec<&dyn Signer> = vec![&payer];
+    signers.extend(multisig_signers.iter().map(|signer| signer as &dyn Signer));
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[set_authority_ix],
+        Some(&payer.pubkey()),
+        &signers,
+        recent_blockhash,
+    );
+
+    let signature = client.send_and_confirm_transaction(&transaction).await?;
+
+    match new_authority {
+        Some(new_authority) => println!(
+            "\n{} {:?} {} {}",
+            style("Successfully set").green(),
+            authority_type,
+            style("authority to").green(),
+            style(new_authority).yellow()
+        ),
+        None => println!(
+            "\n{} {:?} {}",
+            style("Successfully revoked").green(),
+            authority_type,
+            style("authority").green()
+        ),
+    }
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
+
+#[test]
+fn test_close_authority_defaults_to_owner() {
+    use solana_sdk::program_pack::Pack;
+
+    let owner = Pubkey::new_unique();
+    let mut data = vec![0; Account::LEN];
+    Account::pack(
+        Account {
+            mint: Pubkey::new_unique(),
+            owner,
+            state: spl_token_2022::state::AccountState::Initialized,
+            ..Default::default()
+        },
+        &mut data,
+    )
+    .unwrap();
+
+    let (authority_type, current) = current_authority(&data, AuthorityKind::Close).unwrap();
+    assert_eq!(authority_type, AuthorityType::CloseAccount);
+    assert_eq!(current, COption::Some(owner));
+}
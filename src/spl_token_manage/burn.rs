@@ -0,0 +1,103 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::token_program::{get_token_program_id, MultisigArgs};
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+/// burn tokens from a token account
+#[derive(Parser, Debug)]
+pub struct BurnArgs {
+    /// Token mint address
+    #[clap(short, long)]
+    pub mint: String,
+
+    /// Amount to burn
+    #[clap(short, long)]
+    pub amount: f64,
+
+    /// Token account to burn from (optional, uses the owner's associated token account)
+    #[clap(short, long)]
+    pub token_account: Option<String>,
+
+    /// Payer and owner keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    #[clap(flatten)]
+    pub multisig: MultisigArgs,
+}
+
+pub async fn handle_burn(args: &BurnArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let (owner, multisig_signers) = args.multisig.resolve(&payer)?;
+
+    let mint: Pubkey = args.mint.parse()?;
+    let token_program_id = get_token_program_id(&client, &mint).await?;
+    let token_account = match &args.token_account {
+        Some(token_account) => token_account.parse()?,
+        None => get_associated_token_address_with_program_id(&owner, &mint, &token_program_id),
+    };
+
+    let supply_before = client.get_token_supply(&mint).await?;
+    let decimals = supply_before.decimals;
+    let amount = spl_token::ui_amount_to_amount(args.amount, decimals);
+
+    let balance = client.get_token_account_balance(&token_account).await?;
+    if balance.amount.parse::<u64>()? < amount {
+        anyhow::bail!(
+            "Insufficient balance in {}: {} available",
+            token_account,
+            balance.ui_amount_string
+        );
+    }
+
+    let signer_pubkeys = multisig_signers
+        .iter()
+        .map(|signer| signer.pubkey())
+        .collect::<Vec<_>>();
+    let burn_ix = spl_token_2022::instruction::burn_checked(
+        &token_program_id,
+        &token_account,
+        &mint,
+        &owner,
+        &signer_pubkeys.iter().collect::<Vec<_>>(),
+        amount,
+        decimals,
+    )?;
+
+    let mut signers: Vec<&dyn Signer> = vec![&payer];
+    signers.extend(multisig_signers.iter().map(|signer| signer as &dyn Signer));
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[burn_ix],
+        Some(&payer.pubkey()),
+        &signers,
+        recent_blockhash,
+    );
+
+    let signature = client.send_and_confirm_transaction(&transaction).await?;
+    let supply_after = client.get_token_supply(&mint).await?;
+
+    println!(
+        "\n{} {} {} tokens from {}",
+        style("Successfully burned").green(),
+        style(args.amount).yellow(),
+        style(&args.mint).cyan(),
+        style(token_account).yellow(),
+    );
+    println!(
+        "{} {} -> {}",
+        style("Supply:").bold().cyan(),
+        style(supply_before.ui_amount_string).yellow(),
+        style(supply_after.ui_amount_string).green()
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
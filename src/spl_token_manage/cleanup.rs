@@ -0,0 +1,215 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::token_program::{get_all_token_accounts, OwnedTokenAccount};
+use crate::utils::{confirm, print_total_recovered, read_keypair_or_default, send_close_batch};
+use crate::wallet_manage::list_wallets::unique_keystore_wallets;
+use clap::Parser;
+use console::style;
+use solana_account_decoder_client_types::token::{UiAccountState, UiExtension};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// close empty token accounts to reclaim their rent
+#[derive(Parser, Debug)]
+pub struct CleanupArgs {
+    /// Wallet keypair path (optional, uses default if not provided)
+    #[clap(short, long, conflicts_with = "all_wallets")]
+    pub keypair: Option<String>,
+
+    /// Clean up every wallet in ~/.config/solana
+    #[clap(long)]
+    pub all_wallets: bool,
+
+    /// Burn balances up to this amount so their accounts can be closed too
+    #[clap(long)]
+    pub burn_dust: Option<f64>,
+
+    /// Number of accounts closed per transaction
+    #[clap(long, default_value = "20")]
+    pub batch_size: usize,
+
+    /// Skip the confirmation prompt
+    #[clap(short, long)]
+    pub yes: bool,
+}
+
+/// A token account that will be closed, burning `dust` first if set
+struct CloseCandidate {
+    address: Pubkey,
+    program_id: Pubkey,
+    mint: Pubkey,
+    lamports: u64,
+    dust: Option<(u64, u8)>,
+    ui_amount: String,
+}
+
+fn close_candidate(
+    token_account: OwnedTokenAccount,
+    burn_dust: Option<f64>,
+) -> Option<CloseCandidate> {
+    let account = &token_account.account;
+    if account.state == UiAccountState::Frozen {
+        return None;
+    }
+    // withheld transfer fees must be harvested before the account closes
+    let has_withheld_fees = account.extensions.iter().any(|extension| {
+        matches!(extension, UiExtension::TransferFeeAmount(fee) if fee.withheld_amount > 0)
+    });
+    if has_withheld_fees {
+        return None;
+    }
+    // only the close authority may close, one foreign authority fails the whole batch
+    if account
+        .close_authority
+        .as_ref()
+        .is_some_and(|close_authority| *close_authority != account.owner)
+    {
+        return None;
+    }
+
+    let amount: u64 = account.token_amount.amount.parse().ok()?;
+    let dust = if amount == 0 {
+        None
+    } else {
+        let max_dust = burn_dust?;
+        let ui_amount = account.token_amount.ui_amount.unwrap_or(f64::MAX);
+        // wrapped SOL can not be burned
+        if account.is_native || ui_amount > max_dust {
+            return None;
+        }
+        Some((amount, account.token_amount.decimals))
+    };
+
+    Some(CloseCandidate {
+        address: token_account.address,
+        program_id: token_account.program_id,
+        mint: account.mint.parse().ok()?,
+        lamports: token_account.lamports,
+        dust,
+        ui_amount: account.token_amount.ui_amount_string.clone(),
+    })
+}
+
+/// close the empty accounts of one wallet, returning the lamports recovered
+async fn cleanup_wallet(
+    client: &RpcClient,
+    wallet: &Keypair,
+    args: &CleanupArgs,
+) -> anyhow::Result<u64> {
+    let owner = wallet.pubkey();
+    println!(
+        "\n{} {}",
+        style("🧹 Cleaning up").bold(),
+        style(owner).yellow()
+    );
+
+    let candidates = get_all_token_accounts(client, &owner)
+        .await?
+        .into_iter()
+        .filter_map(|token_account| close_candidate(token_account, args.burn_dust))
+        .collect::<Vec<_>>();
+
+    if candidates.is_empty() {
+        println!(
+            "{}",
+            style("No closable token accounts found").dim().italic()
+        );
+        return Ok(0);
+    }
+
+    for candidate in &candidates {
+        let note = match candidate.dust {
+            Some(_) => style(format!("burn {} then close", candidate.ui_amount)).magenta(),
+            None => style("empty".to_string()).dim(),
+        };
+        println!(
+            "  → {} {} SOL {}",
+            style(candidate.address).cyan(),
+            style(lamports_to_sol(candidate.lamports)).green(),
+            note
+        );
+    }
+
+    let reclaimable = candidates.iter().map(|c| c.lamports).sum::<u64>();
+    if !args.yes
+        && !confirm(&format!(
+            "Close {} accounts and reclaim {} SOL?",
+            candidates.len(),
+            lamports_to_sol(reclaimable)
+        ))?
+    {
+        println!("{}", style("Skipped").yellow());
+        return Ok(0);
+    }
+
+    // burning doubles the instructions per account
+    let batch_size = if args.burn_dust.is_some() {
+        (args.batch_size / 2).max(1)
+    } else {
+        args.batch_size.max(1)
+    };
+
+    let mut recovered = 0;
+    for batch in candidates.chunks(batch_size) {
+        let mut instructions = vec![];
+        for candidate in batch {
+            if let Some((amount, decimals)) = candidate.dust {
+                instructions.push(spl_token_2022::instruction::burn_checked(
+                    &candidate.program_id,
+                    &candidate.address,
+                    &candidate.mint,
+                    &owner,
+                    &[],
+                    amount,
+                    decimals,
+                )?);
+            }
+            instructions.push(spl_token_2022::instruction::close_account(
+                &candidate.program_id,
+                &candidate.address,
+                &owner,
+                &owner,
+                &[],
+            )?);
+        }
+
+        let lamports = batch.iter().map(|c| c.lamports).sum::<u64>();
+        recovered += send_close_batch(
+            client,
+            wallet,
+            &instructions,
+            &format!("{} accounts", batch.len()),
+            lamports,
+        )
+        .await?;
+    }
+
+    Ok(recovered)
+}
+
+pub async fn handle_cleanup(args: &CleanupArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+
+    let wallets = if args.all_wallets {
+        unique_keystore_wallets()?
+    } else {
+        vec![read_keypair_or_default(args.keypair.as_deref())?]
+    };
+
+    let mut total = 0;
+    for wallet in &wallets {
+        match cleanup_wallet(&client, wallet, args).await {
+            Ok(recovered) => total += recovered,
+            Err(e) => println!(
+                "{} {}",
+                style("ERROR:").red().bold(),
+                style(format!("Cleanup of {} failed: {:?}", wallet.pubkey(), e)).red()
+            ),
+        }
+    }
+
+    print_total_recovered(total);
+
+    Ok(())
+}
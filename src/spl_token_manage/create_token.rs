@@ -1,16 +1,170 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::metadata::{
+    create_metadata_account_v3, find_metadata_pda, validate_metadata_fields, DataV2,
+};
+use crate::utils::read_keypair_or_default;
 use clap::Parser;
+use console::style;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use spl_token_2022::extension::ExtensionType;
+use spl_token_metadata_interface::state::TokenMetadata;
 
 #[derive(Parser, Debug)]
 pub struct CreateTokenArgs {
-    #[clap(short, long)]
+    #[clap(short = 'n', long)]
     pub token_name: String,
-    #[clap(short, long)]
+    #[clap(short = 's', long)]
     pub token_symbol: String,
-    #[clap(short, long)]
+    #[clap(short = 'd', long)]
     pub token_decimals: u8,
+    /// URI of the off-chain metadata json
+    #[clap(short, long, default_value = "")]
+    pub uri: String,
+    /// Create the mint under the Token-2022 program with the metadata extensions
+    #[clap(long)]
+    pub token_2022: bool,
+    /// Set the payer as freeze authority of the mint
+    #[clap(long)]
+    pub enable_freeze: bool,
+    /// Payer and mint authority keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
 }
 
 pub async fn handle_create_token(args: &CreateTokenArgs) -> anyhow::Result<()> {
-    println!("create token: {:?}", args);
+    validate_metadata_fields(&args.token_name, &args.token_symbol, &args.uri)?;
+
+    let client = get_rpc_client()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let mint = Keypair::new();
+    let freeze_authority = args.enable_freeze.then(|| payer.pubkey());
+
+    let (instructions, metadata_address) = if args.token_2022 {
+        let program_id = spl_token_2022::id();
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::MetadataPointer,
+        ])?;
+        // the metadata extension is appended by the token program, so the
+        // mint must already hold the rent for it
+        let token_metadata = TokenMetadata {
+            update_authority: Some(payer.pubkey()).try_into()?,
+            mint: mint.pubkey(),
+            name: args.token_name.clone(),
+            symbol: args.token_symbol.clone(),
+            uri: args.uri.clone(),
+            additional_metadata: vec![],
+        };
+        let lamports = client
+            .get_minimum_balance_for_rent_exemption(space + token_metadata.tlv_size_of()?)
+            .await?;
+
+        let instructions = vec![
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                lamports,
+                space as u64,
+                &program_id,
+            ),
+            spl_token_2022::extension::metadata_pointer::instruction::initialize(
+                &program_id,
+                &mint.pubkey(),
+                Some(payer.pubkey()),
+                Some(mint.pubkey()),
+            )?,
+            spl_token_2022::instruction::initialize_mint2(
+                &program_id,
+                &mint.pubkey(),
+                &payer.pubkey(),
+                freeze_authority.as_ref(),
+                args.token_decimals,
+            )?,
+            spl_token_metadata_interface::instruction::initialize(
+                &program_id,
+                &mint.pubkey(),
+                &payer.pubkey(),
+                &mint.pubkey(),
+                &payer.pubkey(),
+                args.token_name.clone(),
+                args.token_symbol.clone(),
+                args.uri.clone(),
+            ),
+        ];
+        (instructions, mint.pubkey())
+    } else {
+        let program_id = spl_token::id();
+        let space = spl_token::state::Mint::LEN;
+        let lamports = client.get_minimum_balance_for_rent_exemption(space).await?;
+
+        let instructions = vec![
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                lamports,
+                space as u64,
+                &program_id,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &program_id,
+                &mint.pubkey(),
+                &payer.pubkey(),
+                freeze_authority.as_ref(),
+                args.token_decimals,
+            )?,
+            create_metadata_account_v3(
+                &mint.pubkey(),
+                &payer.pubkey(),
+                &payer.pubkey(),
+                &payer.pubkey(),
+                DataV2 {
+                    name: args.token_name.clone(),
+                    symbol: args.token_symbol.clone(),
+                    uri: args.uri.clone(),
+                    seller_fee_basis_points: 0,
+                    creators: None,
+                    collection: None,
+                    uses: None,
+                },
+            )?,
+        ];
+        (instructions, find_metadata_pda(&mint.pubkey()))
+    };
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[&payer, &mint],
+        recent_blockhash,
+    );
+
+    let signature = client.send_and_confirm_transaction(&transaction).await?;
+
+    println!(
+        "\n{} {} ({})",
+        style("Successfully created token").green(),
+        style(&args.token_name).yellow(),
+        style(&args.token_symbol).yellow(),
+    );
+    println!(
+        "{} {}",
+        style("Mint account:").bold().cyan(),
+        style(mint.pubkey()).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Metadata account:").bold().cyan(),
+        style(metadata_address).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Decimals:").bold().cyan(),
+        style(args.token_decimals).green()
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
     Ok(())
 }
@@ -0,0 +1,200 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::token_program::{
+    get_token_account, get_token_program_id, MultisigArgs,
+};
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_option::COption;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+/// allow a delegate to spend a capped amount from a token account
+#[derive(Parser, Debug)]
+pub struct ApproveArgs {
+    /// Token mint address
+    #[clap(short, long)]
+    pub mint: String,
+
+    /// Delegate address
+    #[clap(short, long)]
+    pub delegate: String,
+
+    /// Amount the delegate may transfer
+    #[clap(short, long)]
+    pub amount: f64,
+
+    /// Token account (optional, uses the owner's associated token account)
+    #[clap(short, long)]
+    pub token_account: Option<String>,
+
+    /// Owner keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    #[clap(flatten)]
+    pub multisig: MultisigArgs,
+}
+
+/// remove the delegate of a token account
+#[derive(Parser, Debug)]
+pub struct RevokeArgs {
+    /// Token mint address
+    #[clap(short, long)]
+    pub mint: String,
+
+    /// Token account (optional, uses the owner's associated token account)
+    #[clap(short, long)]
+    pub token_account: Option<String>,
+
+    /// Owner keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    #[clap(flatten)]
+    pub multisig: MultisigArgs,
+}
+
+/// print the current delegate of a token account
+pub fn print_delegate(account: &spl_token_2022::state::Account, decimals: u8) {
+    match account.delegate {
+        COption::Some(delegate) => {
+            println!(
+                "{} {}",
+                style("Delegate:").bold().cyan(),
+                style(delegate).yellow()
+            );
+            println!(
+                "{} {}",
+                style("Delegated amount:").bold().cyan(),
+                style(spl_token::amount_to_ui_amount_string_trimmed(
+                    account.delegated_amount,
+                    decimals
+                ))
+                .green()
+            );
+        }
+        COption::None => {
+            println!(
+                "{} {}",
+                style("Delegate:").bold().cyan(),
+                style("none").dim()
+            );
+        }
+    }
+}
+
+async fn send(
+    client: &RpcClient,
+    instruction: Instruction,
+    payer: &Keypair,
+    multisig_signers: &[Keypair],
+) -> anyhow::Result<Signature> {
+    let mut signers: Vec<&dyn Signer> = vec![payer];
+    signers.extend(multisig_signers.iter().map(|signer| signer as &dyn Signer));
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &signers,
+        recent_blockhash,
+    );
+    Ok(client.send_and_confirm_transaction(&transaction).await?)
+}
+
+pub async fn handle_approve(args: &ApproveArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let (owner, multisig_signers) = args.multisig.resolve(&payer)?;
+
+    let mint: Pubkey = args.mint.parse()?;
+    let delegate: Pubkey = args.delegate.parse()?;
+    let token_program_id = get_token_program_id(&client, &mint).await?;
+    let token_account = match &args.token_account {
+        Some(token_account) => token_account.parse()?,
+        None => get_associated_token_address_with_program_id(&owner, &mint, &token_program_id),
+    };
+
+    let decimals = client.get_token_supply(&mint).await?.decimals;
+    let amount = spl_token::ui_amount_to_amount(args.amount, decimals);
+
+    let (_, account) = get_token_account(&client, &token_account).await?;
+    print_delegate(&account, decimals);
+
+    let signer_pubkeys = multisig_signers
+        .iter()
+        .map(|signer| signer.pubkey())
+        .collect::<Vec<_>>();
+    let approve_ix = spl_token_2022::instruction::approve_checked(
+        &token_program_id,
+        &token_account,
+        &mint,
+        &delegate,
+        &owner,
+        &signer_pubkeys.iter().collect::<Vec<_>>(),
+        amount,
+        decimals,
+    )?;
+
+    let signature = send(&client, approve_ix, &payer, &multisig_signers).await?;
+
+    println!(
+        "\n{} {} {} {} {}",
+        style("Successfully approved").green(),
+        style(delegate).yellow(),
+        style("to spend").green(),
+        style(args.amount).yellow(),
+        style(&args.mint).cyan(),
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
+
+pub async fn handle_revoke(args: &RevokeArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let (owner, multisig_signers) = args.multisig.resolve(&payer)?;
+
+    let mint: Pubkey = args.mint.parse()?;
+    let token_program_id = get_token_program_id(&client, &mint).await?;
+    let token_account = match &args.token_account {
+        Some(token_account) => token_account.parse()?,
+        None => get_associated_token_address_with_program_id(&owner, &mint, &token_program_id),
+    };
+
+    let decimals = client.get_token_supply(&mint).await?.decimals;
+    let (_, account) = get_token_account(&client, &token_account).await?;
+    print_delegate(&account, decimals);
+    if account.delegate.is_none() {
+        println!("{}", style("Nothing to revoke").yellow());
+        return Ok(());
+    }
+
+    let signer_pubkeys = multisig_signers
+        .iter()
+        .map(|signer| signer.pubkey())
+        .collect::<Vec<_>>();
+    let revoke_ix = spl_token_2022::instruction::revoke(
+        &token_program_id,
+        &token_account,
+        &owner,
+        &signer_pubkeys.iter().collect::<Vec<_>>(),
+    )?;
+
+    let signature = send(&client, revoke_ix, &payer, &multisig_signers).await?;
+
+    println!(
+        "\n{} {}",
+        style("Successfully revoked the delegate of").green(),
+        style(token_account).yellow(),
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use console::style;
+use solana_sdk::clock::Epoch;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::default_account_state::DefaultAccountState;
+use spl_token_2022::extension::interest_bearing_mint::InterestBearingConfig;
+use spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use spl_token_2022::extension::mint_close_authority::MintCloseAuthority;
+use spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use spl_token_2022::extension::transfer_hook::TransferHook;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_token_2022::state::AccountState;
+use spl_token_2022::state::Mint;
+
+/// the fee withheld by the transfer-fee extension, if the mint has one
+pub fn calculate_transfer_fee(
+    mint_data: &[u8],
+    epoch: Epoch,
+    amount: u64,
+) -> anyhow::Result<Option<u64>> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    let Ok(fee_config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(None);
+    };
+    let fee = fee_config
+        .calculate_epoch_fee(epoch, amount)
+        .ok_or_else(|| anyhow::anyhow!("Transfer fee overflow"))?;
+    Ok(Some(fee))
+}
+
+/// whether the mint carries the non-transferable flag
+pub fn is_non_transferable(mint_data: &[u8]) -> anyhow::Result<bool> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    Ok(mint
+        .get_extension_types()?
+        .contains(&ExtensionType::NonTransferable))
+}
+
+/// an optional authority, or "none" when it is unset
+pub fn display_authority(authority: Option<Pubkey>) -> String {
+    authority
+        .map(|authority| authority.to_string())
+        .unwrap_or_else(|| "none".to_string())
+}
+
+/// print every token-2022 extension of a mint, classic mints have none
+pub fn print_mint_extensions(mint_data: &[u8], epoch: Epoch) -> anyhow::Result<()> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    let extension_types = mint.get_extension_types()?;
+    if extension_types.is_empty() {
+        return Ok(());
+    }
+
+    println!("{}", style("Extensions:").bold().cyan());
+    for extension_type in extension_types {
+        match extension_type {
+            ExtensionType::TransferFeeConfig => {
+                let config = mint.get_extension::<TransferFeeConfig>()?;
+                let fee = config.get_epoch_fee(epoch);
+                println!(
+                    "  {} {} bps, max {} (withheld {}, authority {})",
+                    style("Transfer fee:").cyan(),
+                    style(u16::from(fee.transfer_fee_basis_points)).yellow(),
+                    style(u64::from(fee.maximum_fee)).yellow(),
+                    u64::from(config.withheld_amount),
+                    display_authority(config.transfer_fee_config_authority.into()),
+                );
+            }
+            ExtensionType::InterestBearingConfig => {
+                let config = mint.get_extension::<InterestBearingConfig>()?;
+                println!(
+                    "  {} {} bps (authority {})",
+                    style("Interest rate:").cyan(),
+                    style(i16::from(config.current_rate)).yellow(),
+                    display_authority(config.rate_authority.into()),
+                );
+            }
+            ExtensionType::NonTransferable => {
+                println!(
+                    "  {} {}",
+                    style("Non-transferable:").cyan(),
+                    style("yes").red()
+                );
+            }
+            ExtensionType::TransferHook => {
+                let hook = mint.get_extension::<TransferHook>()?;
+                println!(
+                    "  {} {} (authority {})",
+                    style("Transfer hook:").cyan(),
+                    style(display_authority(hook.program_id.into())).yellow(),
+                    display_authority(hook.authority.into()),
+                );
+            }
+            ExtensionType::MintCloseAuthority => {
+                let close = mint.get_extension::<MintCloseAuthority>()?;
+                println!(
+                    "  {} {}",
+                    style("Close authority:").cyan(),
+                    style(display_authority(close.close_authority.into())).yellow()
+                );
+            }
+            ExtensionType::PermanentDelegate => {
+                let delegate = mint.get_extension::<PermanentDelegate>()?;
+                println!(
+                    "  {} {}",
+                    style("Permanent delegate:").cyan(),
+                    style(display_authority(delegate.delegate.into())).yellow()
+                );
+            }
+            ExtensionType::DefaultAccountState => {
+                let state = mint.get_extension::<DefaultAccountState>()?;
+                let state = match AccountState::try_from(state.state) {
+                    Ok(AccountState::Frozen) => "frozen",
+                    Ok(AccountState::Initialized) => "initialized",
+                    _ => "uninitialized",
+                };
+                println!(
+                    "  {} {}",
+                    style("Default account state:").cyan(),
+                    style(state).yellow()
+                );
+            }
+            ExtensionType::MetadataPointer => {
+                let pointer = mint.get_extension::<MetadataPointer>()?;
+                println!(
+                    "  {} {}",
+                    style("Metadata pointer:").cyan(),
+                    style(display_authority(pointer.metadata_address.into())).yellow()
+                );
+            }
+            other => {
+                println!("  {}", style(format!("{:?}", other)).cyan());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_calculate_transfer_fee() {
+    use spl_token_2022::extension::transfer_fee::TransferFee;
+    use spl_token_2022::extension::{BaseStateWithExtensionsMut, StateWithExtensionsMut};
+
+    let space =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])
+            .unwrap();
+    let mut data = vec![0u8; space];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    let fee = TransferFee {
+        epoch: 0.into(),
+        maximum_fee: 50.into(),
+        transfer_fee_basis_points: 100.into(),
+    };
+    let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+    config.older_transfer_fee = fee;
+    config.newer_transfer_fee = fee;
+    state.base = Mint {
+        decimals: 6,
+        is_initialized: true,
+        ..Default::default()
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+
+    assert_eq!(calculate_transfer_fee(&data, 0, 1_000).unwrap(), Some(10));
+    assert_eq!(
+        calculate_transfer_fee(&data, 0, 1_000_000).unwrap(),
+        Some(50)
+    );
+    assert!(!is_non_transferable(&data).unwrap());
+}
@@ -0,0 +1,148 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::token_program::{get_token_account, MultisigArgs};
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use solana_sdk::program_option::COption;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::Mint;
+
+/// freeze or thaw a holder's token account
+#[derive(Parser, Debug)]
+pub struct FreezeArgs {
+    /// Token mint address
+    #[clap(short, long)]
+    pub mint: String,
+
+    /// Holder wallet address, its associated token account is used
+    #[clap(short, long, required_unless_present = "token_account")]
+    pub owner: Option<String>,
+
+    /// Token account address
+    #[clap(short, long, conflicts_with = "owner")]
+    pub token_account: Option<String>,
+
+    /// Freeze authority keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    #[clap(flatten)]
+    pub multisig: MultisigArgs,
+}
+
+pub async fn handle_freeze(args: &FreezeArgs, freeze: bool) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let (freeze_authority, multisig_signers) = args.multisig.resolve(&payer)?;
+
+    let mint: Pubkey = args.mint.parse()?;
+    let mint_account = client.get_account(&mint).await?;
+    let token_program_id = mint_account.owner;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&mint_account.data)
+        .map_err(|_| anyhow::anyhow!("{} is not a token mint", mint))?;
+
+    let token_account = match (&args.token_account, &args.owner) {
+        (Some(token_account), _) => token_account.parse()?,
+        (None, Some(owner)) => {
+            get_associated_token_address_with_program_id(&owner.parse()?, &mint, &token_program_id)
+        }
+        (None, None) => anyhow::bail!("Pass either --owner or --token-account"),
+    };
+    let (_, account) = get_token_account(&client, &token_account).await?;
+    if account.mint != mint {
+        anyhow::bail!("{} does not hold mint {}", token_account, mint);
+    }
+
+    println!(
+        "{} {}",
+        style("Freeze authority:").bold().cyan(),
+        style(match mint_state.base.freeze_authority {
+            COption::Some(authority) => authority.to_string(),
+            COption::None => "none".to_string(),
+        })
+        .yellow()
+    );
+    println!(
+        "{} {}",
+        style("Token account:").bold().cyan(),
+        style(token_account).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Frozen:").bold().cyan(),
+        style(account.is_frozen()).yellow()
+    );
+
+    if mint_state.base.freeze_authority != COption::Some(freeze_authority) {
+        anyhow::bail!(
+            "{} is not the freeze authority of {}",
+            freeze_authority,
+            mint
+        );
+    }
+    if account.is_frozen() == freeze {
+        println!(
+            "{}",
+            style(format!(
+                "Account is already {}",
+                if freeze { "frozen" } else { "thawed" }
+            ))
+            .yellow()
+        );
+        return Ok(());
+    }
+
+    let signer_pubkeys = multisig_signers
+        .iter()
+        .map(|signer| signer.pubkey())
+        .collect::<Vec<_>>();
+    let signer_pubkeys = signer_pubkeys.iter().collect::<Vec<_>>();
+    let instruction = if freeze {
+        spl_token_2022::instruction::freeze_account(
+            &token_program_id,
+            &token_account,
+            &mint,
+            &freeze_authority,
+            &signer_pubkeys,
+        )?
+    } else {
+        spl_token_2022::instruction::thaw_account(
+            &token_program_id,
+            &token_account,
+            &mint,
+            &freeze_authority,
+            &signer_pubkeys,
+        )?
+    };
+
+    let mut signers: Vec<&dyn Signer> = vec![&payer];
+    signers.extend(multisig_signers.iter().map(|signer| signer as &dyn Signer));
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &signers,
+        recent_blockhash,
+    );
+
+    let signature = client.send_and_confirm_transaction(&transaction).await?;
+
+    println!(
+        "\n{} {}",
+        style(if freeze {
+            "Successfully froze"
+        } else {
+            "Successfully thawed"
+        })
+        .green(),
+        style(token_account).yellow()
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
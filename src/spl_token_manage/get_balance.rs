@@ -1,4 +1,7 @@
 use crate::config::get_rpc_client;
+use crate::spl_token_manage::delegate::print_delegate;
+use crate::spl_token_manage::extensions::print_mint_extensions;
+use crate::spl_token_manage::token_program::{get_mint_account, get_token_account};
 use crate::utils::default_account;
 use clap::Parser;
 use console::style;
@@ -20,39 +23,7 @@ pub async fn handle_get_balance(args: &GetBalanceArgs) -> anyhow::Result<()> {
 
     if let Some(wallet_pubkey) = &args.wallet_pubkey {
         let target: Pubkey = wallet_pubkey.parse()?;
-        let addr = spl_associated_token_account::get_associated_token_address(&target, &mint_id);
-        let balance = client.get_token_account_balance(&addr).await?;
-
-        println!(
-            "{} {}",
-            style("Wallet pubkey:").bold().cyan(),
-            style(wallet_pubkey.to_string()).yellow()
-        );
-
-        println!(
-            "{} {}",
-            style("Mint account:").bold().cyan(),
-            style(mint_id.to_string()).yellow()
-        );
-
-        println!(
-            "{} {}",
-            style("Associated token account:").bold().cyan(),
-            style(addr.to_string()).yellow()
-        );
-
-        println!(
-            "{} {}",
-            style("Amount:").bold().cyan(),
-            style(balance.ui_amount_string).green()
-        );
-
-        println!(
-            "{} {}",
-            style("Decimals:").bold().cyan(),
-            style(balance.decimals.to_string()).green()
-        );
-        Ok(())
+        check_balance(&client, &target, &mint_id).await
     } else {
         // use default wallet
         check_default_balance(&client, &mint_id).await
@@ -61,8 +32,21 @@ pub async fn handle_get_balance(args: &GetBalanceArgs) -> anyhow::Result<()> {
 
 async fn check_default_balance(client: &RpcClient, mint_id: &Pubkey) -> anyhow::Result<()> {
     let keypair = default_account()?;
-    let target: Pubkey = keypair.pubkey();
-    let addr = spl_associated_token_account::get_associated_token_address(&target, &mint_id);
+    check_balance(client, &keypair.pubkey(), mint_id).await
+}
+
+async fn check_balance(
+    client: &RpcClient,
+    target: &Pubkey,
+    mint_id: &Pubkey,
+) -> anyhow::Result<()> {
+    let mint_account = get_mint_account(client, mint_id).await?;
+    let token_program_id = mint_account.owner;
+    let addr = spl_associated_token_account::get_associated_token_address_with_program_id(
+        target,
+        mint_id,
+        &token_program_id,
+    );
     let balance = client.get_token_account_balance(&addr).await?;
 
     println!(
@@ -77,6 +61,12 @@ async fn check_default_balance(client: &RpcClient, mint_id: &Pubkey) -> anyhow::
         style(mint_id.to_string()).yellow()
     );
 
+    println!(
+        "{} {}",
+        style("Token program:").bold().cyan(),
+        style(token_program_id.to_string()).yellow()
+    );
+
     println!(
         "{} {}",
         style("Associated token account:").bold().cyan(),
@@ -95,5 +85,11 @@ async fn check_default_balance(client: &RpcClient, mint_id: &Pubkey) -> anyhow::
         style(balance.decimals.to_string()).green()
     );
 
+    let (_, account) = get_token_account(client, &addr).await?;
+    print_delegate(&account, balance.decimals);
+
+    let epoch = client.get_epoch_info().await?.epoch;
+    print_mint_extensions(&mint_account.data, epoch)?;
+
     Ok(())
 }
@@ -0,0 +1,267 @@
+use crate::config::get_rpc_client;
+use crate::output::OutputFormat;
+use crate::spl_token_manage::token_program::get_mint_account;
+use clap::Parser;
+use console::style;
+use serde::Serialize;
+use solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{OptionalContext, RpcKeyedAccount};
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+/// snapshot every holder of a mint
+#[derive(Parser, Debug)]
+pub struct HoldersArgs {
+    /// Token mint address
+    pub mint: String,
+
+    /// Owner address to leave out of the snapshot, can be repeated
+    #[clap(short, long)]
+    pub exclude: Vec<String>,
+
+    /// File with one owner address to exclude per line
+    #[clap(long)]
+    pub exclude_file: Option<String>,
+
+    /// Output format
+    #[clap(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+}
+
+#[derive(Debug, Serialize)]
+struct HolderRow<'a> {
+    owner: String,
+    amount: String,
+    raw_amount: u64,
+    slot: u64,
+    mint: &'a str,
+}
+
+/// owner (offset 32) and amount (offset 64) are all we need from each account
+const OWNER_AMOUNT_SLICE: UiDataSliceConfig = UiDataSliceConfig {
+    offset: 32,
+    length: 40,
+};
+
+/// token-2022 accounts with extensions are longer than the base layout and
+/// marked by the account type byte right after it
+const ACCOUNT_TYPE_OFFSET: usize = spl_token_2022::state::Account::LEN;
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// fetch the balances of all token accounts matching the filters, summed up
+/// per owner into `balances`. Returns the slot the query was served at.
+async fn collect_balances(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    filters: Vec<RpcFilterType>,
+    min_context_slot: Option<u64>,
+    balances: &mut HashMap<Pubkey, u64>,
+) -> anyhow::Result<u64> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(OWNER_AMOUNT_SLICE),
+            commitment: Some(client.commitment()),
+            min_context_slot,
+        },
+        with_context: Some(true),
+        sort_results: None,
+    };
+
+    // the typed helper drops the context, so the slot is read from the raw response
+    let response: OptionalContext<Vec<RpcKeyedAccount>> = client
+        .send(
+            RpcRequest::GetProgramAccounts,
+            serde_json::json!([program_id.to_string(), config]),
+        )
+        .await?;
+    let (slot, accounts) = match response {
+        OptionalContext::Context(response) => (response.context.slot, response.value),
+        OptionalContext::NoContext(accounts) => (client.get_slot().await?, accounts),
+    };
+
+    for keyed_account in accounts {
+        let Some(data) = keyed_account.account.data.decode() else {
+            continue;
+        };
+        if data.len() < 40 {
+            continue;
+        }
+        let owner = Pubkey::try_from(&data[..32])?;
+        let amount = u64::from_le_bytes(data[32..40].try_into()?);
+        if amount > 0 {
+            *balances.entry(owner).or_default() += amount;
+        }
+    }
+
+    Ok(slot)
+}
+
+fn read_excluded(args: &HoldersArgs) -> anyhow::Result<HashSet<Pubkey>> {
+    let mut excluded = args
+        .exclude
+        .iter()
+        .map(|address| address.parse())
+        .collect::<Result<HashSet<Pubkey>, _>>()?;
+
+    if let Some(path) = &args.exclude_file {
+        let content = std::fs::read_to_string(path)?;
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            excluded.insert(
+                line.parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid address in {}: {}", path, line))?,
+            );
+        }
+    }
+
+    Ok(excluded)
+}
+
+pub async fn handle_holders(args: &HoldersArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let mint: Pubkey = args.mint.parse()?;
+    let excluded = read_excluded(args)?;
+
+    let mint_account = get_mint_account(&client, &mint).await?;
+    let program_id = mint_account.owner;
+    let decimals =
+        spl_token_2022::extension::StateWithExtensions::<spl_token_2022::state::Mint>::unpack(
+            &mint_account.data,
+        )?
+        .base
+        .decimals;
+
+    let mint_filter = RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, mint.to_bytes().to_vec()));
+    let mut balances = HashMap::new();
+    let slot = collect_balances(
+        &client,
+        &program_id,
+        vec![
+            RpcFilterType::DataSize(spl_token::state::Account::LEN as u64),
+            mint_filter.clone(),
+        ],
+        None,
+        &mut balances,
+    )
+    .await?;
+    if program_id == spl_token_2022::id() {
+        let extended_slot = collect_balances(
+            &client,
+            &program_id,
+            vec![
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    ACCOUNT_TYPE_OFFSET,
+                    vec![ACCOUNT_TYPE_ACCOUNT],
+                )),
+                mint_filter,
+            ],
+            Some(slot),
+            &mut balances,
+        )
+        .await?;
+        // the two queries can not be pinned to one slot, say so when they differ
+        if extended_slot != slot {
+            eprintln!(
+                "{} {}",
+                style("WARNING:").yellow().bold(),
+                style(format!(
+                    "Accounts with extensions were read at slot {}, the others at slot {}",
+                    extended_slot, slot
+                ))
+                .yellow()
+            );
+        }
+    }
+
+    let mut holders = balances
+        .into_iter()
+        .filter(|(owner, _)| !excluded.contains(owner))
+        .collect::<Vec<_>>();
+    holders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    // the balances of every holder are already in memory, only the formatted
+    // rows are written one by one instead of building the whole document
+    let rows = holders.iter().map(|(owner, amount)| HolderRow {
+        owner: owner.to_string(),
+        amount: spl_token::amount_to_ui_amount_string_trimmed(*amount, decimals),
+        raw_amount: *amount,
+        slot,
+        mint: &args.mint,
+    });
+
+    match args.output {
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(std::io::stdout().lock());
+            for row in rows {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+        }
+        OutputFormat::Json => {
+            let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+            write!(
+                out,
+                "{{\"mint\":{},\"slot\":{},\"holders\":[",
+                serde_json::to_string(&args.mint)?,
+                slot
+            )?;
+            for (i, row) in rows.enumerate() {
+                if i > 0 {
+                    write!(out, ",")?;
+                }
+                serde_json::to_writer(&mut out, &row)?;
+            }
+            writeln!(out, "]}}")?;
+            out.flush()?;
+        }
+        OutputFormat::Table => {
+            let total = holders.iter().map(|(_, amount)| amount).sum::<u64>();
+            println!(
+                "\n{} {} {} {}",
+                style("👥 Holders of").bold().underlined(),
+                style(&args.mint).yellow(),
+                style("at slot").dim(),
+                style(slot).cyan()
+            );
+            for row in rows {
+                println!(
+                    "  → {} {:>24}",
+                    style(&row.owner).cyan(),
+                    style(&row.amount).green()
+                );
+            }
+            println!(
+                "\n{} {}",
+                style("Holders:").bold().cyan(),
+                style(holders.len()).yellow()
+            );
+            println!(
+                "{} {}",
+                style("Total held:").bold().cyan(),
+                style(spl_token::amount_to_ui_amount_string_trimmed(
+                    total, decimals
+                ))
+                .green()
+            );
+            if !excluded.is_empty() {
+                println!(
+                    "{} {}",
+                    style("Excluded owners:").bold().cyan(),
+                    style(excluded.len()).yellow()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
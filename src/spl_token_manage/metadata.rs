@@ -0,0 +1,287 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+use spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+/// Metaplex Token Metadata program
+pub const MPL_TOKEN_METADATA_ID: Pubkey =
+    solana_sdk::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+pub const MAX_NAME_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct Uses {
+    pub use_method: u8,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct DataV2 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
+
+/// Metaplex metadata account, up to the fields this tool reads
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct MetaplexMetadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
+
+impl MetaplexMetadata {
+    /// decode a metadata account, ignoring the trailing fields
+    pub fn decode(data: &[u8]) -> anyhow::Result<Self> {
+        let mut metadata = Self::deserialize(&mut &data[..])
+            .map_err(|e| anyhow::anyhow!("Failed to decode metadata account: {}", e))?;
+        // on-chain strings are padded with NUL bytes
+        metadata.name = metadata.name.trim_end_matches('\0').to_string();
+        metadata.symbol = metadata.symbol.trim_end_matches('\0').to_string();
+        metadata.uri = metadata.uri.trim_end_matches('\0').to_string();
+        Ok(metadata)
+    }
+
+    pub fn to_data_v2(&self) -> DataV2 {
+        DataV2 {
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            uri: self.uri.clone(),
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            creators: self.creators.clone(),
+            collection: self.collection.clone(),
+            uses: self.uses.clone(),
+        }
+    }
+}
+
+#[derive(BorshSerialize)]
+struct CreateMetadataAccountV3Args {
+    data: DataV2,
+    is_mutable: bool,
+    collection_details: Option<u8>,
+}
+
+#[derive(BorshSerialize)]
+struct UpdateMetadataAccountV2Args {
+    data: Option<DataV2>,
+    new_update_authority: Option<Pubkey>,
+    primary_sale_happened: Option<bool>,
+    is_mutable: Option<bool>,
+}
+
+/// Metaplex metadata PDA of a mint
+pub fn find_metadata_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", MPL_TOKEN_METADATA_ID.as_ref(), mint.as_ref()],
+        &MPL_TOKEN_METADATA_ID,
+    )
+    .0
+}
+
+pub fn create_metadata_account_v3(
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    update_authority: &Pubkey,
+    data: DataV2,
+) -> anyhow::Result<Instruction> {
+    let mut ix_data = vec![CREATE_METADATA_ACCOUNT_V3];
+    ix_data.extend(borsh::to_vec(&CreateMetadataAccountV3Args {
+        data,
+        is_mutable: true,
+        collection_details: None,
+    })?);
+
+    Ok(Instruction {
+        program_id: MPL_TOKEN_METADATA_ID,
+        accounts: vec![
+            AccountMeta::new(find_metadata_pda(mint), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*update_authority, update_authority == payer),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: ix_data,
+    })
+}
+
+pub fn update_metadata_account_v2(
+    metadata: &Pubkey,
+    update_authority: &Pubkey,
+    data: DataV2,
+) -> anyhow::Result<Instruction> {
+    let mut ix_data = vec![UPDATE_METADATA_ACCOUNT_V2];
+    ix_data.extend(borsh::to_vec(&UpdateMetadataAccountV2Args {
+        data: Some(data),
+        new_update_authority: None,
+        primary_sale_happened: None,
+        is_mutable: None,
+    })?);
+
+    Ok(Instruction {
+        program_id: MPL_TOKEN_METADATA_ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*update_authority, true),
+        ],
+        data: ix_data,
+    })
+}
+
+/// check the metaplex length limits before sending anything
+pub fn validate_metadata_fields(name: &str, symbol: &str, uri: &str) -> anyhow::Result<()> {
+    if name.len() > MAX_NAME_LENGTH {
+        anyhow::bail!("Token name must be at most {} bytes", MAX_NAME_LENGTH);
+    }
+    if symbol.len() > MAX_SYMBOL_LENGTH {
+        anyhow::bail!("Token symbol must be at most {} bytes", MAX_SYMBOL_LENGTH);
+    }
+    if uri.len() > MAX_URI_LENGTH {
+        anyhow::bail!("Metadata URI must be at most {} bytes", MAX_URI_LENGTH);
+    }
+    Ok(())
+}
+
+/// Where the metadata of a mint is stored
+#[derive(Debug, Clone)]
+pub enum MetadataSource {
+    /// Token-2022 token-metadata extension inside the mint account
+    Token2022Extension(TokenMetadata),
+    /// Metaplex metadata PDA
+    Metaplex(MetaplexMetadata),
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenMetadataInfo {
+    pub address: Pubkey,
+    pub update_authority: Option<Pubkey>,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub source: MetadataSource,
+}
+
+/// fetch the metadata of a mint, looking at the token-2022 extension first
+/// and then at the metaplex PDA
+pub async fn fetch_token_metadata(
+    client: &RpcClient,
+    mint: &Pubkey,
+) -> anyhow::Result<Option<TokenMetadataInfo>> {
+    let mint_account = client.get_account(mint).await?;
+
+    if mint_account.owner == spl_token_2022::id() {
+        let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data)?;
+        if let Ok(token_metadata) = state.get_variable_len_extension::<TokenMetadata>() {
+            return Ok(Some(TokenMetadataInfo {
+                address: *mint,
+                update_authority: Option::<Pubkey>::from(token_metadata.update_authority),
+                name: token_metadata.name.clone(),
+                symbol: token_metadata.symbol.clone(),
+                uri: token_metadata.uri.clone(),
+                source: MetadataSource::Token2022Extension(token_metadata),
+            }));
+        }
+        // the pointer may reference an account of another program
+        if let Ok(pointer) = state.get_extension::<MetadataPointer>() {
+            if let Some(address) = Option::<Pubkey>::from(pointer.metadata_address) {
+                if address != *mint {
+                    return fetch_metaplex_metadata(client, &address).await;
+                }
+            }
+        }
+    }
+
+    fetch_metaplex_metadata(client, &find_metadata_pda(mint)).await
+}
+
+async fn fetch_metaplex_metadata(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> anyhow::Result<Option<TokenMetadataInfo>> {
+    let Some(account) = client
+        .get_account_with_commitment(address, client.commitment())
+        .await?
+        .value
+    else {
+        return Ok(None);
+    };
+    if account.owner != MPL_TOKEN_METADATA_ID {
+        return Ok(None);
+    }
+
+    let metadata = MetaplexMetadata::decode(&account.data)?;
+    Ok(Some(TokenMetadataInfo {
+        address: *address,
+        update_authority: Some(metadata.update_authority),
+        name: metadata.name.clone(),
+        symbol: metadata.symbol.clone(),
+        uri: metadata.uri.clone(),
+        source: MetadataSource::Metaplex(metadata),
+    }))
+}
+
+#[test]
+fn test_metaplex_metadata_roundtrip() {
+    let mint = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let mut data = vec![4u8];
+    data.extend(authority.to_bytes());
+    data.extend(mint.to_bytes());
+    let mut padded_name = "Token".to_string();
+    padded_name.push_str(&"\0".repeat(MAX_NAME_LENGTH - 5));
+    data.extend(borsh::to_vec(&padded_name).unwrap());
+    data.extend(borsh::to_vec(&"TKN".to_string()).unwrap());
+    data.extend(borsh::to_vec(&"https://example.com".to_string()).unwrap());
+    data.extend(0u16.to_le_bytes());
+    // creators, primary_sale_happened, is_mutable, then zero padding
+    data.extend([0u8, 0, 1]);
+    data.extend([0u8; 64]);
+
+    let metadata = MetaplexMetadata::decode(&data).unwrap();
+    assert_eq!(metadata.update_authority, authority);
+    assert_eq!(metadata.mint, mint);
+    assert_eq!(metadata.name, "Token");
+    assert_eq!(metadata.symbol, "TKN");
+    assert!(metadata.is_mutable);
+    assert!(metadata.collection.is_none());
+}
@@ -0,0 +1,155 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::extensions::{display_authority, print_mint_extensions};
+use crate::spl_token_manage::metadata::{fetch_token_metadata, MetadataSource};
+use crate::spl_token_manage::token_program::get_mint_account;
+use clap::Parser;
+use console::style;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::{Account, Mint};
+
+/// decode a mint account with its extensions, metadata and top holders
+#[derive(Parser, Debug)]
+pub struct MintInfoArgs {
+    /// Token mint address
+    pub mint: String,
+
+    /// Number of largest accounts to show (at most 20)
+    #[clap(short, long, default_value = "10")]
+    pub top: usize,
+}
+
+pub async fn handle_mint_info(args: &MintInfoArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let mint_address: Pubkey = args.mint.parse()?;
+
+    let mint_account = get_mint_account(&client, &mint_address).await?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_account.data)?.base;
+    let program = if mint_account.owner == spl_token_2022::id() {
+        "Token-2022"
+    } else {
+        "SPL Token"
+    };
+
+    println!(
+        "\n{} {}",
+        style("🪙 Mint").bold().underlined(),
+        style(mint_address).yellow()
+    );
+    println!(
+        "{} {} ({})",
+        style("Program:").bold().cyan(),
+        style(program).yellow(),
+        style(mint_account.owner).dim()
+    );
+    println!(
+        "{} {}",
+        style("Supply:").bold().cyan(),
+        style(spl_token::amount_to_ui_amount_string_trimmed(
+            mint.supply,
+            mint.decimals
+        ))
+        .green()
+    );
+    println!(
+        "{} {}",
+        style("Decimals:").bold().cyan(),
+        style(mint.decimals).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Mint authority:").bold().cyan(),
+        style(display_authority(mint.mint_authority.into())).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Freeze authority:").bold().cyan(),
+        style(display_authority(mint.freeze_authority.into())).yellow()
+    );
+
+    let epoch = client.get_epoch_info().await?.epoch;
+    print_mint_extensions(&mint_account.data, epoch)?;
+
+    if let Some(metadata) = fetch_token_metadata(&client, &mint_address).await? {
+        let source = match metadata.source {
+            MetadataSource::Token2022Extension(_) => "token-2022 extension",
+            MetadataSource::Metaplex(_) => "metaplex",
+        };
+        println!("{}", style("Metadata:").bold().cyan());
+        println!(
+            "  {} {} ({})",
+            style("Name:").cyan(),
+            style(&metadata.name).green(),
+            style(source).dim()
+        );
+        println!(
+            "  {} {}",
+            style("Symbol:").cyan(),
+            style(&metadata.symbol).green()
+        );
+        println!(
+            "  {} {}",
+            style("URI:").cyan(),
+            style(&metadata.uri).green()
+        );
+    }
+
+    print_largest_accounts(&client, &mint_address, &mint, args.top).await?;
+    println!();
+
+    Ok(())
+}
+
+async fn print_largest_accounts(
+    client: &RpcClient,
+    mint_address: &Pubkey,
+    mint: &Mint,
+    top: usize,
+) -> anyhow::Result<()> {
+    let largest = client.get_token_largest_accounts(mint_address).await?;
+    let largest = largest.into_iter().take(top).collect::<Vec<_>>();
+    if largest.is_empty() {
+        return Ok(());
+    }
+
+    // the rpc only returns token accounts, their owners are read in one batch
+    let addresses = largest
+        .iter()
+        .map(|balance| balance.address.parse())
+        .collect::<Result<Vec<Pubkey>, _>>()?;
+    let owners = client
+        .get_multiple_accounts(&addresses)
+        .await?
+        .into_iter()
+        .map(|account| {
+            account
+                .and_then(|account| {
+                    StateWithExtensions::<Account>::unpack(&account.data)
+                        .ok()
+                        .map(|state| state.base.owner.to_string())
+                })
+                .unwrap_or_else(|| "?".to_string())
+        })
+        .collect::<Vec<_>>();
+
+    println!("{}", style("Top holders:").bold().cyan());
+    for (i, (balance, owner)) in largest.iter().zip(owners).enumerate() {
+        let amount = balance.amount.amount.parse::<u64>().unwrap_or(0);
+        let share = if mint.supply == 0 {
+            0.0
+        } else {
+            amount as f64 / mint.supply as f64 * 100.0
+        };
+        println!(
+            "  {:>2}. {} {:>20} {:>7} {}",
+            i + 1,
+            style(owner).yellow(),
+            style(&balance.amount.ui_amount_string).green(),
+            style(format!("{:.2}%", share)).cyan(),
+            style(&balance.address).dim()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::token_program::{get_token_program_id, MultisigArgs};
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+/// mint new tokens to a wallet
+#[derive(Parser, Debug)]
+pub struct MintToArgs {
+    /// Token mint address
+    #[clap(short, long)]
+    pub mint: String,
+
+    /// Amount to mint
+    #[clap(short, long)]
+    pub amount: f64,
+
+    /// Recipient wallet address (optional, mints to the payer if not provided)
+    #[clap(short, long)]
+    pub recipient: Option<String>,
+
+    /// Payer and mint authority keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+
+    #[clap(flatten)]
+    pub multisig: MultisigArgs,
+}
+
+pub async fn handle_mint_to(args: &MintToArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let payer = read_keypair_or_default(args.keypair.as_deref())?;
+    let (mint_authority, multisig_signers) = args.multisig.resolve(&payer)?;
+
+    let mint: Pubkey = args.mint.parse()?;
+    let recipient = match &args.recipient {
+        Some(recipient) => recipient.parse()?,
+        None => payer.pubkey(),
+    };
+    let token_program_id = get_token_program_id(&client, &mint).await?;
+
+    let supply_before = client.get_token_supply(&mint).await?;
+    let decimals = supply_before.decimals;
+    let amount = spl_token::ui_amount_to_amount(args.amount, decimals);
+
+    let recipient_ata =
+        get_associated_token_address_with_program_id(&recipient, &mint, &token_program_id);
+
+    let mut instructions = vec![];
+    if client
+        .get_account_with_commitment(&recipient_ata, client.commitment())
+        .await?
+        .value
+        .is_none()
+    {
+        println!(
+            "{} {}",
+            style("Creating associated token account").dim(),
+            style(recipient_ata).yellow()
+        );
+        instructions.push(create_associated_token_account_idempotent(
+            &payer.pubkey(),
+            &recipient,
+            &mint,
+            &token_program_id,
+        ));
+    }
+
+    let signer_pubkeys = multisig_signers
+        .iter()
+        .map(|signer| signer.pubkey())
+        .collect::<Vec<_>>();
+    instructions.push(spl_token_2022::instruction::mint_to_checked(
+        &token_program_id,
+        &mint,
+        &recipient_ata,
+        &mint_authority,
+        &signer_pubkeys.iter().collect::<Vec<_>>(),
+        amount,
+        decimals,
+    )?);
+
+    let mut signers: Vec<&dyn Signer> = vec![&payer];
+    signers.extend(multisig_signers.iter().map(|signer| signer as &dyn Signer));
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        recent_blockhash,
+    );
+
+    let signature = client.send_and_confirm_transaction(&transaction).await?;
+    let supply_after = client.get_token_supply(&mint).await?;
+
+    println!(
+        "\n{} {} {} tokens to {}",
+        style("Successfully minted").green(),
+        style(args.amount).yellow(),
+        style(&args.mint).cyan(),
+        style(recipient).yellow(),
+    );
+    println!(
+        "{} {} -> {}",
+        style("Supply:").bold().cyan(),
+        style(supply_before.ui_amount_string).yellow(),
+        style(supply_after.ui_amount_string).green()
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
@@ -0,0 +1,235 @@
+use crate::config::get_rpc_client;
+use crate::output::{print_csv, print_json, OutputFormat};
+use crate::spl_token_manage::metadata::fetch_token_metadata;
+use crate::spl_token_manage::token_program::{get_all_token_accounts, OwnedTokenAccount};
+use crate::utils::default_account;
+use clap::Parser;
+use console::style;
+use futures::StreamExt;
+use serde::Serialize;
+use solana_account_decoder_client_types::token::UiAccountState;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use std::collections::BTreeMap;
+
+/// list every token holding of a wallet
+#[derive(Parser, Debug)]
+pub struct PortfolioArgs {
+    /// Wallet address (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub wallet: Option<String>,
+
+    /// Output format
+    #[clap(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+}
+
+/// metadata accounts fetched at the same time
+const METADATA_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Serialize)]
+pub struct PortfolioAccount {
+    pub mint: String,
+    pub symbol: String,
+    pub token_account: String,
+    pub token_program: String,
+    pub associated: bool,
+    pub amount: String,
+    pub decimals: u8,
+    pub frozen: bool,
+    pub delegate: Option<String>,
+    pub delegated_amount: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PortfolioHolding {
+    pub mint: String,
+    pub symbol: String,
+    pub name: String,
+    pub total: String,
+    pub accounts: Vec<PortfolioAccount>,
+}
+
+pub async fn handle_portfolio(args: &PortfolioArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let wallet: Pubkey = match &args.wallet {
+        Some(wallet) => wallet.parse()?,
+        None => default_account()?.pubkey(),
+    };
+
+    // group the accounts by mint, the BTreeMap keeps the output stable
+    let mut by_mint: BTreeMap<String, Vec<_>> = BTreeMap::new();
+    for token_account in get_all_token_accounts(&client, &wallet).await? {
+        by_mint
+            .entry(token_account.account.mint.clone())
+            .or_default()
+            .push(token_account);
+    }
+
+    // buffered keeps the mint order while fetching in parallel
+    let metadata = futures::stream::iter(by_mint.keys().map(|mint| {
+        let client = &client;
+        async move {
+            let metadata = match mint.parse::<Pubkey>() {
+                Ok(address) => fetch_token_metadata(client, &address).await,
+                Err(e) => Err(e.into()),
+            };
+            metadata.unwrap_or_else(|e| {
+                eprintln!(
+                    "{} {}",
+                    style("WARNING:").yellow().bold(),
+                    style(format!("Failed to fetch the metadata of {}: {}", mint, e)).yellow()
+                );
+                None
+            })
+        }
+    }))
+    .buffered(METADATA_CONCURRENCY)
+    .collect::<Vec<_>>()
+    .await;
+
+    let mut holdings = vec![];
+    for ((mint, accounts), metadata) in by_mint.into_iter().zip(metadata) {
+        let (name, symbol) = metadata
+            .map(|metadata| (metadata.name, metadata.symbol))
+            .unwrap_or_default();
+        let decimals = accounts
+            .first()
+            .map(|token_account| token_account.account.token_amount.decimals)
+            .unwrap_or_default();
+        let total = accounts
+            .iter()
+            .map(|token_account| {
+                token_account
+                    .account
+                    .token_amount
+                    .amount
+                    .parse::<u64>()
+                    .unwrap_or(0)
+            })
+            .sum::<u64>();
+
+        let accounts = accounts
+            .into_iter()
+            .map(|token_account| to_portfolio_account(&wallet, &symbol, token_account))
+            .collect();
+
+        holdings.push(PortfolioHolding {
+            mint,
+            symbol,
+            name,
+            total: spl_token::amount_to_ui_amount_string_trimmed(total, decimals),
+            accounts,
+        });
+    }
+
+    match args.output {
+        OutputFormat::Json => print_json(&holdings),
+        OutputFormat::Csv => print_csv(holdings.into_iter().flat_map(|holding| holding.accounts)),
+        OutputFormat::Table => {
+            print_portfolio_table(&wallet, &holdings);
+            Ok(())
+        }
+    }
+}
+
+fn to_portfolio_account(
+    wallet: &Pubkey,
+    symbol: &str,
+    token_account: OwnedTokenAccount,
+) -> PortfolioAccount {
+    let OwnedTokenAccount {
+        address,
+        program_id,
+        account,
+        ..
+    } = token_account;
+    let associated = account
+        .mint
+        .parse()
+        .map(|mint| get_associated_token_address_with_program_id(wallet, &mint, &program_id))
+        .is_ok_and(|ata| ata == address);
+
+    PortfolioAccount {
+        mint: account.mint,
+        symbol: symbol.to_string(),
+        token_account: address.to_string(),
+        token_program: if program_id == spl_token_2022::id() {
+            "token-2022".to_string()
+        } else {
+            "spl-token".to_string()
+        },
+        associated,
+        amount: account.token_amount.ui_amount_string,
+        decimals: account.token_amount.decimals,
+        frozen: account.state == UiAccountState::Frozen,
+        delegate: account.delegate,
+        delegated_amount: account
+            .delegated_amount
+            .map(|amount| amount.ui_amount_string),
+    }
+}
+
+fn print_portfolio_table(wallet: &Pubkey, holdings: &[PortfolioHolding]) {
+    println!(
+        "\n{} {}",
+        style("💼 Portfolio of").bold().underlined(),
+        style(wallet).yellow()
+    );
+
+    if holdings.is_empty() {
+        println!("{}", style("No token accounts found").dim().italic());
+        println!();
+        return;
+    }
+
+    for holding in holdings {
+        let symbol = if holding.symbol.is_empty() {
+            "?".to_string()
+        } else {
+            holding.symbol.clone()
+        };
+        println!(
+            "\n{} {} {}",
+            style(format!("{:<10}", symbol)).bold().cyan(),
+            style(&holding.mint).yellow(),
+            style(&holding.total).green().bold()
+        );
+
+        for account in &holding.accounts {
+            let mut flags = vec![];
+            if !account.associated {
+                flags.push(style("non-ATA".to_string()).dim());
+            }
+            if account.token_program == "token-2022" {
+                flags.push(style("token-2022".to_string()).dim());
+            }
+            if account.frozen {
+                flags.push(style("frozen".to_string()).red());
+            }
+            if let Some(delegate) = &account.delegate {
+                flags.push(
+                    style(format!(
+                        "delegated {} to {}",
+                        account.delegated_amount.clone().unwrap_or_default(),
+                        delegate
+                    ))
+                    .magenta(),
+                );
+            }
+
+            let flags = flags
+                .iter()
+                .map(|flag| flag.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            println!(
+                "  → {} {:>20} {}",
+                style(&account.token_account).dim(),
+                style(&account.amount).green(),
+                flags
+            );
+        }
+    }
+    println!();
+}
@@ -0,0 +1,95 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::metadata::{fetch_token_metadata, MetadataSource};
+use clap::Parser;
+use console::style;
+use solana_sdk::pubkey::Pubkey;
+
+/// show the on-chain metadata of a token
+#[derive(Parser, Debug)]
+pub struct ShowMetadataArgs {
+    /// Token mint address
+    #[clap(short, long)]
+    pub mint: String,
+}
+
+pub async fn handle_show_metadata(args: &ShowMetadataArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let mint: Pubkey = args.mint.parse()?;
+
+    let Some(metadata) = fetch_token_metadata(&client, &mint).await? else {
+        println!(
+            "{} {}",
+            style("No metadata found for mint").yellow(),
+            style(mint).cyan()
+        );
+        return Ok(());
+    };
+
+    let source = match &metadata.source {
+        MetadataSource::Token2022Extension(_) => "Token-2022 metadata extension",
+        MetadataSource::Metaplex(_) => "Metaplex Token Metadata",
+    };
+
+    println!(
+        "{} {}",
+        style("Mint account:").bold().cyan(),
+        style(mint).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Metadata account:").bold().cyan(),
+        style(metadata.address).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Source:").bold().cyan(),
+        style(source).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Update authority:").bold().cyan(),
+        style(
+            metadata
+                .update_authority
+                .map(|authority| authority.to_string())
+                .unwrap_or_else(|| "none".to_string())
+        )
+        .yellow()
+    );
+    println!(
+        "{} {}",
+        style("Name:").bold().cyan(),
+        style(&metadata.name).green()
+    );
+    println!(
+        "{} {}",
+        style("Symbol:").bold().cyan(),
+        style(&metadata.symbol).green()
+    );
+    println!(
+        "{} {}",
+        style("URI:").bold().cyan(),
+        style(&metadata.uri).green()
+    );
+
+    match &metadata.source {
+        MetadataSource::Token2022Extension(token_metadata) => {
+            for (key, value) in &token_metadata.additional_metadata {
+                println!(
+                    "{} {}",
+                    style(format!("{}:", key)).bold().cyan(),
+                    style(value).green()
+                );
+            }
+        }
+        MetadataSource::Metaplex(metaplex) => {
+            println!(
+                "{} {}",
+                style("Mutable:").bold().cyan(),
+                style(metaplex.is_mutable).green()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use clap::Parser;
+use solana_account_decoder_client_types::token::{TokenAccountType, UiTokenAccount};
+use solana_account_decoder_client_types::UiAccountData;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use spl_token_2022::extension::StateWithExtensions;
+
+/// fetch a mint account, making sure it belongs to one of the token programs
+pub async fn get_mint_account(client: &RpcClient, mint: &Pubkey) -> anyhow::Result<Account> {
+    let account = client.get_account(mint).await?;
+    if account.owner == spl_token::id() || account.owner == spl_token_2022::id() {
+        Ok(account)
+    } else {
+        anyhow::bail!(
+            "{} is not a token mint, it is owned by {}",
+            mint,
+            account.owner
+        )
+    }
+}
+
+/// the token program that owns the given mint
+pub async fn get_token_program_id(client: &RpcClient, mint: &Pubkey) -> anyhow::Result<Pubkey> {
+    Ok(get_mint_account(client, mint).await?.owner)
+}
+
+/// Options for authorities held by an SPL multisig account
+#[derive(Parser, Debug)]
+pub struct MultisigArgs {
+    /// Multisig account acting as the authority
+    #[clap(long)]
+    pub multisig: Option<String>,
+
+    /// Keypair path of a multisig signer, repeat for every required signer
+    #[clap(long = "multisig-signer")]
+    pub multisig_signers: Vec<String>,
+}
+
+impl MultisigArgs {
+    /// Resolve the authority pubkey and the extra signers. Without a
+    /// multisig the given keypair is the authority.
+    pub fn resolve(&self, authority: &Keypair) -> anyhow::Result<(Pubkey, Vec<Keypair>)> {
+        let Some(multisig) = &self.multisig else {
+            return Ok((authority.pubkey(), vec![]));
+        };
+        if self.multisig_signers.is_empty() {
+            anyhow::bail!("--multisig requires at least one --multisig-signer");
+        }
+
+        let signers = self
+            .multisig_signers
+            .iter()
+            .map(|path| {
+                read_keypair_file(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to read keypair file {}: {}", path, e))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok((multisig.parse()?, signers))
+    }
+}
+
+/// fetch and decode a token account of either token program
+pub async fn get_token_account(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> anyhow::Result<(Pubkey, spl_token_2022::state::Account)> {
+    let account = client.get_account(address).await?;
+    if account.owner != spl_token::id() && account.owner != spl_token_2022::id() {
+        anyhow::bail!("{} is not a token account", address);
+    }
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+        .map_err(|_| anyhow::anyhow!("{} is not a token account", address))?;
+    Ok((account.owner, state.base))
+}
+
+/// A token account found by [`get_all_token_accounts`]
+#[derive(Debug)]
+pub struct OwnedTokenAccount {
+    pub address: Pubkey,
+    pub program_id: Pubkey,
+    pub lamports: u64,
+    pub account: UiTokenAccount,
+}
+
+/// every token account of a wallet under both token programs, ATA or not
+pub async fn get_all_token_accounts(
+    client: &RpcClient,
+    owner: &Pubkey,
+) -> anyhow::Result<Vec<OwnedTokenAccount>> {
+    let mut token_accounts = vec![];
+    for program_id in [spl_token::id(), spl_token_2022::id()] {
+        let accounts = client
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program_id))
+            .await?;
+        for keyed_account in accounts {
+            let lamports = keyed_account.account.lamports;
+            let UiAccountData::Json(parsed) = keyed_account.account.data else {
+                continue;
+            };
+            if let TokenAccountType::Account(account) = serde_json::from_value(parsed.parsed)? {
+                token_accounts.push(OwnedTokenAccount {
+                    address: keyed_account.pubkey.parse()?,
+                    program_id,
+                    lamports,
+                    account,
+                });
+            }
+        }
+    }
+    Ok(token_accounts)
+}
@@ -1,12 +1,22 @@
 use anyhow::Result;
 use console::style;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::program_option::COption;
 use solana_sdk::transaction::Transaction;
-use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file, signer::Signer};
-use spl_associated_token_account::get_associated_token_address;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token::native_mint;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::offchain::AccountFetchError;
+use spl_token_2022::state::Mint;
 use std::str::FromStr;
 
 use crate::config::get_rpc_client;
-use crate::utils::default_account;
+use crate::spl_token_manage::extensions::{calculate_transfer_fee, is_non_transferable};
+use crate::spl_token_manage::token_program::{get_mint_account, get_token_account};
+use crate::spl_token_manage::wrap::wrap_instructions;
+use crate::utils::read_keypair_or_default;
 
 #[derive(Debug, clap::Parser)]
 pub struct TransferTokenArgs {
@@ -25,45 +35,194 @@ pub struct TransferTokenArgs {
     /// Amount to transfer
     #[clap(short, long)]
     pub amount: f64,
+
+    /// Delegate keypair path, transfers from the owner's account as its approved delegate
+    #[clap(long, requires = "owner", conflicts_with = "source")]
+    pub delegate_signer: Option<String>,
+
+    /// Owner wallet address of the source account when transferring as a delegate
+    #[clap(long, requires = "delegate_signer")]
+    pub owner: Option<String>,
+
+    /// For wSOL transfers, wrap the missing amount from the plain SOL balance
+    #[clap(long, conflicts_with = "delegate_signer")]
+    pub auto_wrap: bool,
+
+    /// Create the recipient's associated token account if missing, paid by the sender
+    #[clap(long)]
+    pub fund_recipient: bool,
 }
 
 pub async fn handle_transfer_token(args: &TransferTokenArgs) -> Result<()> {
     let client = get_rpc_client()?;
 
-    // Get source keypair (default or specified)
-    let source_keypair = match &args.source {
-        Some(key) => read_keypair_file(key)
-            .map_err(|e| anyhow::anyhow!("Failed to read keypair file: {}", e))?,
-        None => default_account()?,
+    // Get the signing authority, either the source wallet or its delegate
+    let (source_keypair, source_owner) = match (&args.delegate_signer, &args.owner) {
+        (Some(delegate_signer), Some(owner)) => (
+            read_keypair_or_default(Some(delegate_signer))?,
+            Pubkey::from_str(owner)?,
+        ),
+        _ => {
+            let keypair = read_keypair_or_default(args.source.as_deref())?;
+            let owner = keypair.pubkey();
+            (keypair, owner)
+        }
     };
 
     // Parse destination and mint addresses
     let destination = Pubkey::from_str(&args.destination)?;
     let mint = Pubkey::from_str(&args.mint)?;
 
+    // The owning program decides between spl-token and token-2022
+    let mint_account = get_mint_account(&client, &mint).await?;
+    let token_program_id = mint_account.owner;
+    if is_non_transferable(&mint_account.data)? {
+        anyhow::bail!("Mint {} is non-transferable", mint);
+    }
+
     // Get token decimals for amount calculation
-    let token_account = client.get_token_supply(&mint).await?;
-    let decimals = token_account.decimals;
-    let amount = (args.amount * 10f64.powi(decimals as i32)) as u64;
+    let decimals = StateWithExtensions::<Mint>::unpack(&mint_account.data)?
+        .base
+        .decimals;
+    let amount = spl_token::ui_amount_to_amount(args.amount, decimals);
 
     // Get source and destination token accounts
-    let source_ata = get_associated_token_address(&source_keypair.pubkey(), &mint);
-    let destination_ata = get_associated_token_address(&destination, &mint);
-
-    // Create transfer instruction
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
-        &source_ata,
-        &destination_ata,
-        &source_keypair.pubkey(),
-        &[&source_keypair.pubkey()],
-        amount,
-    )?;
+    let source_ata =
+        get_associated_token_address_with_program_id(&source_owner, &mint, &token_program_id);
+    let destination_ata =
+        get_associated_token_address_with_program_id(&destination, &mint, &token_program_id);
+
+    if args.delegate_signer.is_some() {
+        let (_, source_account) = get_token_account(&client, &source_ata).await?;
+        if source_account.delegate != COption::Some(source_keypair.pubkey()) {
+            anyhow::bail!(
+                "{} is not the delegate of {}",
+                source_keypair.pubkey(),
+                source_ata
+            );
+        }
+        if source_account.delegated_amount < amount {
+            anyhow::bail!(
+                "Delegated amount {} is less than the transfer amount",
+                spl_token::amount_to_ui_amount_string_trimmed(
+                    source_account.delegated_amount,
+                    decimals
+                )
+            );
+        }
+    }
+
+    let mut instructions = vec![];
+
+    // wSOL can be topped up from the SOL balance in the same transaction
+    if mint == native_mint::id() {
+        let wrapped = match client.get_token_account_balance(&source_ata).await {
+            Ok(balance) => balance.amount.parse::<u64>()?,
+            Err(_) => 0,
+        };
+        if wrapped < amount {
+            if !args.auto_wrap {
+                anyhow::bail!(
+                    "Only {} wSOL available, pass --auto-wrap to wrap the rest from SOL",
+                    lamports_to_sol(wrapped)
+                );
+            }
+            println!(
+                "{} {} {}",
+                style("Wrapping").bold().cyan(),
+                style(lamports_to_sol(amount - wrapped)).yellow(),
+                style("SOL").cyan()
+            );
+            instructions.extend(wrap_instructions(
+                &source_keypair.pubkey(),
+                &source_owner,
+                amount - wrapped,
+            )?);
+        }
+    }
+
+    if args.fund_recipient {
+        instructions.push(create_associated_token_account_idempotent(
+            &source_keypair.pubkey(),
+            &destination,
+            &mint,
+            &token_program_id,
+        ));
+    } else if client
+        .get_account_with_commitment(&destination_ata, client.commitment())
+        .await?
+        .value
+        .is_none()
+    {
+        anyhow::bail!(
+            "Recipient token account {} does not exist, pass --fund-recipient to create it",
+            destination_ata
+        );
+    }
+
+    // Transfer hooks need their extra accounts resolved from chain
+    let fetch_account_data = |address: Pubkey| {
+        let client = &client;
+        async move {
+            client
+                .get_account_with_commitment(&address, client.commitment())
+                .await
+                .map(|response| response.value.map(|account| account.data))
+                .map_err(|e| Box::new(e) as AccountFetchError)
+        }
+    };
+
+    let epoch = client.get_epoch_info().await?.epoch;
+    let fee = calculate_transfer_fee(&mint_account.data, epoch, amount)?;
+    let transfer_ix = match fee {
+        Some(fee) => {
+            println!(
+                "{} {} ({} {})",
+                style("Transfer fee:").bold().cyan(),
+                style(spl_token::amount_to_ui_amount(fee, decimals)).yellow(),
+                style("recipient receives").dim(),
+                style(spl_token::amount_to_ui_amount(
+                    amount.saturating_sub(fee),
+                    decimals
+                ))
+                .green(),
+            );
+            spl_token_2022::offchain::create_transfer_checked_with_fee_instruction_with_extra_metas(
+                &token_program_id,
+                &source_ata,
+                &mint,
+                &destination_ata,
+                &source_keypair.pubkey(),
+                &[],
+                amount,
+                decimals,
+                fee,
+                fetch_account_data,
+            )
+            .await
+        }
+        None => {
+            spl_token_2022::offchain::create_transfer_checked_instruction_with_extra_metas(
+                &token_program_id,
+                &source_ata,
+                &mint,
+                &destination_ata,
+                &source_keypair.pubkey(),
+                &[],
+                amount,
+                decimals,
+                fetch_account_data,
+            )
+            .await
+        }
+    }
+    .map_err(|e| anyhow::anyhow!("Failed to build transfer instruction: {}", e))?;
+    instructions.push(transfer_ix);
 
     // Create and send transaction
     let recent_blockhash = client.get_latest_blockhash().await?;
     let transaction = Transaction::new_signed_with_payer(
-        &[transfer_ix],
+        &instructions,
         Some(&source_keypair.pubkey()),
         &[&source_keypair],
         recent_blockhash,
@@ -76,7 +235,7 @@ pub async fn handle_transfer_token(args: &TransferTokenArgs) -> Result<()> {
         style("Successfully transferred").green(),
         style(args.amount).yellow(),
         style(&args.mint).cyan(),
-        style(source_keypair.pubkey()).yellow(),
+        style(source_owner).yellow(),
         style(&args.destination).yellow(),
     );
     println!("{}: {}", style("Transaction signature").cyan(), signature);
@@ -0,0 +1,141 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::metadata::{
+    fetch_token_metadata, update_metadata_account_v2, validate_metadata_fields, MetadataSource,
+};
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use spl_token_metadata_interface::state::Field;
+
+/// update the on-chain name, symbol or uri of a token
+#[derive(Parser, Debug)]
+pub struct UpdateMetadataArgs {
+    /// Token mint address
+    #[clap(short, long)]
+    pub mint: String,
+    /// New token name
+    #[clap(short, long)]
+    pub name: Option<String>,
+    /// New token symbol
+    #[clap(short, long)]
+    pub symbol: Option<String>,
+    /// New metadata URI
+    #[clap(short, long)]
+    pub uri: Option<String>,
+    /// Update authority keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+}
+
+pub async fn handle_update_metadata(args: &UpdateMetadataArgs) -> anyhow::Result<()> {
+    if args.name.is_none() && args.symbol.is_none() && args.uri.is_none() {
+        anyhow::bail!("Nothing to update, pass at least one of --name, --symbol or --uri");
+    }
+
+    let client = get_rpc_client()?;
+    let authority = read_keypair_or_default(args.keypair.as_deref())?;
+    let mint: Pubkey = args.mint.parse()?;
+
+    let metadata = fetch_token_metadata(&client, &mint)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No metadata found for mint {}", mint))?;
+
+    if metadata.update_authority != Some(authority.pubkey()) {
+        anyhow::bail!(
+            "{} is not the update authority of {}",
+            authority.pubkey(),
+            mint
+        );
+    }
+
+    let instructions = match metadata.source {
+        MetadataSource::Token2022Extension(mut token_metadata) => {
+            let old_size = token_metadata.tlv_size_of()?;
+            let mut instructions = vec![];
+            let fields = [
+                (Field::Name, &args.name),
+                (Field::Symbol, &args.symbol),
+                (Field::Uri, &args.uri),
+            ];
+            for (field, value) in fields {
+                if let Some(value) = value {
+                    token_metadata.update(field.clone(), value.clone());
+                    instructions.push(spl_token_metadata_interface::instruction::update_field(
+                        &spl_token_2022::id(),
+                        &metadata.address,
+                        &authority.pubkey(),
+                        field,
+                        value.clone(),
+                    ));
+                }
+            }
+
+            // top up the mint before the extension grows
+            let new_size = token_metadata.tlv_size_of()?;
+            if new_size > old_size {
+                let account = client.get_account(&metadata.address).await?;
+                let required = client
+                    .get_minimum_balance_for_rent_exemption(
+                        account.data.len() + new_size - old_size,
+                    )
+                    .await?;
+                if required > account.lamports {
+                    instructions.insert(
+                        0,
+                        system_instruction::transfer(
+                            &authority.pubkey(),
+                            &metadata.address,
+                            required - account.lamports,
+                        ),
+                    );
+                }
+            }
+            instructions
+        }
+        MetadataSource::Metaplex(metaplex) => {
+            let mut data = metaplex.to_data_v2();
+            if let Some(name) = &args.name {
+                data.name = name.clone();
+            }
+            if let Some(symbol) = &args.symbol {
+                data.symbol = symbol.clone();
+            }
+            if let Some(uri) = &args.uri {
+                data.uri = uri.clone();
+            }
+            validate_metadata_fields(&data.name, &data.symbol, &data.uri)?;
+            if !metaplex.is_mutable {
+                anyhow::bail!("Metadata of {} is immutable", mint);
+            }
+
+            vec![update_metadata_account_v2(
+                &metadata.address,
+                &authority.pubkey(),
+                data,
+            )?]
+        }
+    };
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&authority.pubkey()),
+        &[&authority],
+        recent_blockhash,
+    );
+
+    let signature = client.send_and_confirm_transaction(&transaction).await?;
+
+    println!(
+        "\n{} {}",
+        style("Successfully updated metadata of").green(),
+        style(mint).yellow(),
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use crate::config::get_rpc_client;
+use crate::spl_token_manage::token_program::get_token_account;
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::{lamports_to_sol, sol_to_lamports};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token::native_mint;
+
+/// wrap SOL into the native mint (wSOL)
+#[derive(Parser, Debug)]
+pub struct WrapArgs {
+    /// Amount of SOL to wrap
+    pub amount: f64,
+
+    /// Wallet keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+}
+
+/// unwrap wSOL back into SOL by closing the token account
+#[derive(Parser, Debug)]
+pub struct UnwrapArgs {
+    /// wSOL token account (optional, uses the associated token account)
+    #[clap(short, long)]
+    pub token_account: Option<String>,
+
+    /// Wallet keypair path (optional, uses default if not provided)
+    #[clap(short, long)]
+    pub keypair: Option<String>,
+}
+
+/// create (if needed) and fund the native-mint ATA of `owner`
+pub fn wrap_instructions(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    lamports: u64,
+) -> anyhow::Result<Vec<Instruction>> {
+    let wsol_account = get_associated_token_address(owner, &native_mint::id());
+    Ok(vec![
+        create_associated_token_account_idempotent(
+            payer,
+            owner,
+            &native_mint::id(),
+            &spl_token::id(),
+        ),
+        system_instruction::transfer(owner, &wsol_account, lamports),
+        spl_token::instruction::sync_native(&spl_token::id(), &wsol_account)?,
+    ])
+}
+
+pub async fn handle_wrap(args: &WrapArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let keypair = read_keypair_or_default(args.keypair.as_deref())?;
+    let owner = keypair.pubkey();
+    let lamports = sol_to_lamports(args.amount);
+
+    let balance = client.get_balance(&owner).await?;
+    if balance < lamports {
+        anyhow::bail!(
+            "Insufficient SOL balance: {} SOL available",
+            lamports_to_sol(balance)
+        );
+    }
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &wrap_instructions(&owner, &owner, lamports)?,
+        Some(&owner),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client.send_and_confirm_transaction(&transaction).await?;
+    let wsol_account = get_associated_token_address(&owner, &native_mint::id());
+    let wsol_balance = client.get_token_account_balance(&wsol_account).await?;
+
+    println!(
+        "\n{} {} {}",
+        style("Successfully wrapped").green(),
+        style(args.amount).yellow(),
+        style("SOL").cyan()
+    );
+    println!(
+        "{} {}",
+        style("wSOL account:").bold().cyan(),
+        style(wsol_account).yellow()
+    );
+    println!(
+        "{} {}",
+        style("wSOL balance:").bold().cyan(),
+        style(wsol_balance.ui_amount_string).green()
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
+
+pub async fn handle_unwrap(args: &UnwrapArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let keypair = read_keypair_or_default(args.keypair.as_deref())?;
+    let owner = keypair.pubkey();
+
+    let wsol_account = match &args.token_account {
+        Some(token_account) => token_account.parse()?,
+        None => get_associated_token_address(&owner, &native_mint::id()),
+    };
+    let (program_id, account) = get_token_account(&client, &wsol_account).await?;
+    if account.mint != native_mint::id() {
+        anyhow::bail!("{} is not a wSOL account", wsol_account);
+    }
+
+    let close_ix = spl_token_2022::instruction::close_account(
+        &program_id,
+        &wsol_account,
+        &owner,
+        &owner,
+        &[],
+    )?;
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&owner),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client.send_and_confirm_transaction(&transaction).await?;
+
+    println!(
+        "\n{} {} {}",
+        style("Successfully unwrapped").green(),
+        style(lamports_to_sol(account.amount)).yellow(),
+        style("SOL").cyan()
+    );
+    println!("{}: {}", style("Transaction signature").cyan(), signature);
+
+    Ok(())
+}
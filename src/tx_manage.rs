@@ -0,0 +1,40 @@
+use clap::Parser;
+
+pub mod build;
+pub mod decode;
+pub mod export;
+pub mod history;
+pub mod inspect;
+pub mod send;
+pub mod show;
+pub mod status;
+
+#[derive(Parser, Debug)]
+pub enum TxManage {
+    /// query the status of one or more transactions
+    Status(status::StatusArgs),
+    /// list the transactions of an address
+    History(history::HistoryArgs),
+    /// decode and print a confirmed transaction
+    Show(show::ShowArgs),
+    /// decode a serialized transaction offline
+    Inspect(inspect::InspectArgs),
+    /// broadcast a serialized transaction and track it to confirmation
+    Send(send::SendArgs),
+    /// export an accounting ledger of every balance change of a wallet
+    Export(export::ExportArgs),
+    /// assemble a transaction from a json spec of raw instructions
+    Build(build::BuildArgs),
+}
+
+pub async fn handle_tx_manage(tx_manage: &TxManage) -> anyhow::Result<()> {
+    match tx_manage {
+        TxManage::Status(args) => status::handle_status(args).await,
+        TxManage::History(args) => history::handle_history(args).await,
+        TxManage::Show(args) => show::handle_show(args).await,
+        TxManage::Inspect(args) => inspect::handle_inspect(args),
+        TxManage::Send(args) => send::handle_send(args).await,
+        TxManage::Export(args) => export::handle_export(args).await,
+        TxManage::Build(args) => build::handle_build(args).await,
+    }
+}
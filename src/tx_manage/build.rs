@@ -0,0 +1,293 @@
+use crate::config::get_rpc_client;
+use crate::program_manage::idl::{encode, Idl};
+use crate::tx_manage::inspect::print_transaction;
+use crate::tx_manage::send::{broadcast, simulate};
+use crate::tx_manage::status::Commitment;
+use crate::utils::{confirm, decode_hex, default_account};
+use crate::wallet_manage::list_wallets::read_keystore;
+use clap::Parser;
+use console::style;
+use serde::Deserialize;
+use serde_json::Value;
+use solana_packet::PACKET_DATA_SIZE;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+
+/// assemble a transaction from a json spec of raw instructions
+#[derive(Parser, Debug)]
+pub struct BuildArgs {
+    /// Json file with the payer and the instructions to assemble
+    #[clap(long)]
+    pub spec: String,
+
+    /// Simulate the transaction before asking to send it
+    #[clap(long)]
+    pub simulate: bool,
+
+    /// Skip the confirmation prompt
+    #[clap(short, long)]
+    pub yes: bool,
+
+    /// Skip the preflight check of the rpc node
+    #[clap(long)]
+    pub skip_preflight: bool,
+
+    /// Commitment to wait for
+    #[clap(short, long, value_enum, default_value_t = Commitment::Confirmed)]
+    pub commitment: Commitment,
+
+    /// Seconds to wait for the confirmation
+    #[clap(short, long, default_value = "60")]
+    pub timeout: u64,
+}
+
+/// The spec file, a bare array of instructions is accepted too
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Spec {
+    /// keystore wallet name or address paying the fee, the default wallet if absent
+    #[serde(default)]
+    payer: Option<String>,
+    instructions: Vec<SpecInstruction>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpecInstruction {
+    program_id: String,
+    #[serde(default)]
+    accounts: Vec<SpecAccount>,
+    #[serde(default)]
+    data: Option<SpecData>,
+}
+
+/// `pubkey` is an address or the name of a keystore wallet
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpecAccount {
+    pubkey: String,
+    #[serde(default, alias = "is_signer")]
+    signer: bool,
+    #[serde(default, alias = "is_writable")]
+    writable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SpecData {
+    Hex(String),
+    Base58(String),
+    /// typed values, borsh encoded one after the other
+    Fields(Vec<SpecField>),
+}
+
+/// a value and its type, written as in an Anchor IDL: `u64`, `pubkey`,
+/// `string`, `{"vec": "u8"}`, `{"option": "u32"}` and so on
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpecField {
+    #[serde(rename = "type")]
+    ty: Value,
+    value: Value,
+}
+
+fn parse_spec(content: &str) -> anyhow::Result<Spec> {
+    let value: Value = serde_json::from_str(content)?;
+    let value = match value {
+        Value::Array(_) => serde_json::json!({ "instructions": value }),
+        value => value,
+    };
+    Ok(serde_json::from_value(value)?)
+}
+
+fn build_instruction(
+    spec: &SpecInstruction,
+    resolve: &impl Fn(&str) -> anyhow::Result<Pubkey>,
+) -> anyhow::Result<Instruction> {
+    let accounts = spec
+        .accounts
+        .iter()
+        .map(|account| {
+            let pubkey = resolve(&account.pubkey)?;
+            Ok(if account.writable {
+                AccountMeta::new(pubkey, account.signer)
+            } else {
+                AccountMeta::new_readonly(pubkey, account.signer)
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let data = match &spec.data {
+        None => vec![],
+        Some(SpecData::Hex(hex)) => decode_hex(hex)?,
+        Some(SpecData::Base58(data)) => bs58::decode(data).into_vec()?,
+        Some(SpecData::Fields(fields)) => {
+            let idl = Idl::default();
+            let mut data = vec![];
+            for (i, field) in fields.iter().enumerate() {
+                encode(&idl, &field.ty, &field.value, &mut data)
+                    .map_err(|e| anyhow::anyhow!("Field #{}: {}", i + 1, e))?;
+            }
+            data
+        }
+    };
+
+    Ok(Instruction {
+        program_id: resolve(&spec.program_id)?,
+        accounts,
+        data,
+    })
+}
+
+/// the keystore wallets, found by file name with or without `.json`
+struct Keystore(Vec<(String, Keypair)>);
+
+impl Keystore {
+    fn wallet(&self, name: &str) -> Option<&Keypair> {
+        self.0
+            .iter()
+            .find(|(file_name, _)| {
+                file_name == name || file_name.strip_suffix(".json") == Some(name)
+            })
+            .map(|(_, keypair)| keypair)
+    }
+
+    fn resolve(&self, name: &str) -> anyhow::Result<Pubkey> {
+        if let Ok(pubkey) = name.parse() {
+            return Ok(pubkey);
+        }
+        self.wallet(name)
+            .map(|keypair| keypair.pubkey())
+            .ok_or_else(|| anyhow::anyhow!("{} is neither an address nor a keystore wallet", name))
+    }
+
+    fn keypair(&self, pubkey: &Pubkey) -> Option<&Keypair> {
+        self.0
+            .iter()
+            .map(|(_, keypair)| keypair)
+            .find(|keypair| keypair.pubkey() == *pubkey)
+    }
+}
+
+pub async fn handle_build(args: &BuildArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let content = std::fs::read_to_string(&args.spec)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", args.spec, e))?;
+    let spec = parse_spec(&content).map_err(|e| anyhow::anyhow!("Invalid spec: {}", e))?;
+    if spec.instructions.is_empty() {
+        anyhow::bail!("The spec has no instructions");
+    }
+
+    let keystore = Keystore(read_keystore()?);
+    let payer = match &spec.payer {
+        Some(payer) => {
+            let pubkey = keystore.resolve(payer)?;
+            keystore
+                .keypair(&pubkey)
+                .ok_or_else(|| anyhow::anyhow!("No keystore wallet for the payer {}", payer))?
+                .insecure_clone()
+        }
+        None => default_account()?,
+    };
+
+    let instructions = spec
+        .instructions
+        .iter()
+        .enumerate()
+        .map(|(i, instruction)| {
+            build_instruction(instruction, &|name| keystore.resolve(name))
+                .map_err(|e| anyhow::anyhow!("Instruction #{}: {}", i + 1, e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let message =
+        Message::new_with_blockhash(&instructions, Some(&payer.pubkey()), &recent_blockhash);
+    let required = &message.account_keys[..message.header.num_required_signatures as usize];
+    let mut signers = vec![];
+    for signer in required {
+        let keypair = if *signer == payer.pubkey() {
+            &payer
+        } else {
+            keystore
+                .keypair(signer)
+                .ok_or_else(|| anyhow::anyhow!("No keystore wallet for the signer {}", signer))?
+        };
+        signers.push(keypair);
+    }
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.try_sign(&signers, recent_blockhash)?;
+    let transaction = VersionedTransaction::from(transaction);
+
+    print_transaction(&transaction);
+    let size = bincode::serialized_size(&transaction)? as usize;
+    if size > PACKET_DATA_SIZE {
+        anyhow::bail!(
+            "The transaction is {} bytes, at most {} fit in a packet",
+            size,
+            PACKET_DATA_SIZE
+        );
+    }
+
+    if args.simulate {
+        simulate(&client, &transaction, Default::default()).await?;
+    }
+    if !args.yes && !confirm("Send this transaction?")? {
+        println!("{}", style("Aborted").yellow());
+        return Ok(());
+    }
+
+    broadcast(
+        &client,
+        &transaction,
+        args.skip_preflight,
+        args.commitment,
+        args.timeout,
+    )
+    .await
+}
+
+#[test]
+fn test_build_instruction() {
+    let owner = Pubkey::new_unique();
+    let resolve = |name: &str| -> anyhow::Result<Pubkey> {
+        match name {
+            "alice" => Ok(owner),
+            name => Ok(name.parse()?),
+        }
+    };
+
+    let spec = parse_spec(
+        r#"[
+            {
+                "program_id": "11111111111111111111111111111111",
+                "accounts": [
+                    {"pubkey": "alice", "signer": true, "writable": true},
+                    {"pubkey": "SysvarRent111111111111111111111111111111111"}
+                ],
+                "data": {"fields": [
+                    {"type": "u32", "value": 2},
+                    {"type": "u64", "value": "1000"}
+                ]}
+            },
+            {"program_id": "11111111111111111111111111111111", "data": {"hex": "0x0aff"}}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(spec.payer, None);
+
+    let transfer = build_instruction(&spec.instructions[0], &resolve).unwrap();
+    assert_eq!(transfer.accounts[0], AccountMeta::new(owner, true));
+    assert!(!transfer.accounts[1].is_writable && !transfer.accounts[1].is_signer);
+    assert_eq!(
+        transfer.data,
+        [&2u32.to_le_bytes()[..], &1000u64.to_le_bytes()].concat()
+    );
+
+    let raw = build_instruction(&spec.instructions[1], &resolve).unwrap();
+    assert_eq!(raw.data, [10, 255]);
+    assert!(parse_spec(r#"[{"program_id": "x", "acounts": []}]"#).is_err());
+}
@@ -0,0 +1,338 @@
+use console::style;
+use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::stake::instruction::StakeInstruction;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::vote::instruction::VoteInstruction;
+use solana_sdk::{pubkey, stake, system_program, vote};
+
+pub const MEMO_V1_ID: Pubkey = pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+pub const MEMO_V2_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// An account of a message with the role it plays in the transaction
+#[derive(Debug, Clone)]
+pub struct MessageAccount {
+    pub pubkey: Pubkey,
+    pub signer: bool,
+    pub writable: bool,
+    /// set for accounts loaded from an address lookup table
+    pub lookup_table: Option<Pubkey>,
+}
+
+/// An instruction decoded into something a human can read
+#[derive(Debug)]
+pub struct DecodedInstruction {
+    pub program: String,
+    pub name: String,
+    pub args: Option<String>,
+    /// label and address of every account, unresolved lookups included
+    pub accounts: Vec<(String, String)>,
+    /// hex encoded data of instructions that could not be decoded
+    pub raw_data: Option<String>,
+}
+
+/// the accounts of a message in index order. `loaded` holds the writable and
+/// readonly addresses resolved from lookup tables, in that order.
+pub fn message_accounts(
+    message: &VersionedMessage,
+    loaded: Option<(&[Pubkey], &[Pubkey])>,
+) -> Vec<MessageAccount> {
+    let header = message.header();
+    let static_keys = message.static_account_keys();
+    let signers = header.num_required_signatures as usize;
+    let writable_signers = signers.saturating_sub(header.num_readonly_signed_accounts as usize);
+    let writable_unsigned = static_keys
+        .len()
+        .saturating_sub(header.num_readonly_unsigned_accounts as usize);
+
+    let mut accounts = static_keys
+        .iter()
+        .enumerate()
+        .map(|(i, pubkey)| MessageAccount {
+            pubkey: *pubkey,
+            signer: i < signers,
+            writable: if i < signers {
+                i < writable_signers
+            } else {
+                i < writable_unsigned
+            },
+            lookup_table: None,
+        })
+        .collect::<Vec<_>>();
+
+    let Some((writable, readonly)) = loaded else {
+        return accounts;
+    };
+    // lookups list the writable indexes of every table before the readonly ones
+    let lookups = message.address_table_lookups().unwrap_or_default();
+    let writable_tables = lookups
+        .iter()
+        .flat_map(|lookup| lookup.writable_indexes.iter().map(|_| lookup.account_key));
+    let readonly_tables = lookups
+        .iter()
+        .flat_map(|lookup| lookup.readonly_indexes.iter().map(|_| lookup.account_key));
+    for (pubkey, table) in writable.iter().zip(writable_tables) {
+        accounts.push(MessageAccount {
+            pubkey: *pubkey,
+            signer: false,
+            writable: true,
+            lookup_table: Some(table),
+        });
+    }
+    for (pubkey, table) in readonly.iter().zip(readonly_tables) {
+        accounts.push(MessageAccount {
+            pubkey: *pubkey,
+            signer: false,
+            writable: false,
+            lookup_table: Some(table),
+        });
+    }
+    accounts
+}
+
+/// split the debug output of an instruction enum into the variant and its fields
+fn split_debug(debug: String) -> (String, Option<String>) {
+    match debug.find([' ', '(', '{']) {
+        Some(position) => {
+            let args = debug[position..].trim().to_string();
+            (debug[..position].to_string(), Some(args))
+        }
+        None => (debug, None),
+    }
+}
+
+fn account_labels(program_id: &Pubkey, name: &str) -> &'static [&'static str] {
+    if *program_id == system_program::id() {
+        match name {
+            "CreateAccount" => &["funding account", "new account"],
+            "Assign" | "AssignWithSeed" => &["assigned account", "base account"],
+            "Transfer" => &["from", "to"],
+            "CreateAccountWithSeed" => &["funding account", "created account", "base account"],
+            "AdvanceNonceAccount" => &["nonce account", "recent blockhashes", "nonce authority"],
+            "WithdrawNonceAccount" => &[
+                "nonce account",
+                "recipient",
+                "recent blockhashes",
+                "rent sysvar",
+                "nonce authority",
+            ],
+            "InitializeNonceAccount" => &["nonce account", "recent blockhashes", "rent sysvar"],
+            "AuthorizeNonceAccount" => &["nonce account", "nonce authority"],
+            "Allocate" | "AllocateWithSeed" => &["new account", "base account"],
+            "TransferWithSeed" => &["from", "base account", "to"],
+            _ => &[],
+        }
+    } else if *program_id == spl_token::id() || *program_id == spl_token_2022::id() {
+        match name {
+            "InitializeMint" => &["mint", "rent sysvar"],
+            "InitializeMint2" => &["mint"],
+            "InitializeAccount" => &["account", "mint", "owner", "rent sysvar"],
+            // the owner of these two is instruction data, not an account
+            "InitializeAccount2" => &["account", "mint", "rent sysvar"],
+            "InitializeAccount3" => &["account", "mint"],
+            "Transfer" => &["source", "destination", "authority"],
+            "TransferChecked" => &["source", "mint", "destination", "authority"],
+            "Approve" => &["source", "delegate", "owner"],
+            "ApproveChecked" => &["source", "mint", "delegate", "owner"],
+            "Revoke" => &["source", "owner"],
+            "SetAuthority" => &["account", "current authority"],
+            "MintTo" | "MintToChecked" => &["mint", "destination", "mint authority"],
+            "Burn" | "BurnChecked" => &["account", "mint", "authority"],
+            "CloseAccount" => &["account", "destination", "authority"],
+            "FreezeAccount" | "ThawAccount" => &["account", "mint", "freeze authority"],
+            "SyncNative" => &["account"],
+            _ => &[],
+        }
+    } else if *program_id == spl_associated_token_account::id() {
+        match name {
+            "Create" | "CreateIdempotent" => &[
+                "funding account",
+                "associated account",
+                "wallet",
+                "mint",
+                "system program",
+                "token program",
+            ],
+            "RecoverNested" => &[
+                "nested account",
+                "nested mint",
+                "destination",
+                "owner account",
+                "owner mint",
+                "wallet",
+                "token program",
+            ],
+            _ => &[],
+        }
+    } else if *program_id == stake::program::id() {
+        match name {
+            "Initialize" | "InitializeChecked" => &["stake account", "rent sysvar"],
+            "DelegateStake" => &[
+                "stake account",
+                "vote account",
+                "clock",
+                "stake history",
+                "stake config",
+                "stake authority",
+            ],
+            "Deactivate" => &["stake account", "clock", "stake authority"],
+            "Withdraw" => &[
+                "stake account",
+                "recipient",
+                "clock",
+                "stake history",
+                "withdraw authority",
+            ],
+            "Split" => &["stake account", "split account", "stake authority"],
+            "Merge" => &[
+                "destination account",
+                "source account",
+                "clock",
+                "stake history",
+                "stake authority",
+            ],
+            _ => &[],
+        }
+    } else if *program_id == MEMO_V1_ID || *program_id == MEMO_V2_ID {
+        &["signer"]
+    } else {
+        &[]
+    }
+}
+
+/// the variant name and fields of a known program's instruction
+fn decode_known(
+    program_id: &Pubkey,
+    data: &[u8],
+) -> Option<(&'static str, String, Option<String>)> {
+    let decoded = if *program_id == system_program::id() {
+        let instruction = bincode::deserialize::<SystemInstruction>(data).ok()?;
+        ("System Program", split_debug(format!("{:?}", instruction)))
+    } else if *program_id == spl_token::id() {
+        let instruction = spl_token::instruction::TokenInstruction::unpack(data).ok()?;
+        ("SPL Token", split_debug(format!("{:?}", instruction)))
+    } else if *program_id == spl_token_2022::id() {
+        let instruction = spl_token_2022::instruction::TokenInstruction::unpack(data).ok()?;
+        ("Token-2022", split_debug(format!("{:?}", instruction)))
+    } else if *program_id == spl_associated_token_account::id() {
+        let name = match data.first() {
+            None | Some(0) => "Create",
+            Some(1) => "CreateIdempotent",
+            Some(2) => "RecoverNested",
+            Some(_) => return None,
+        };
+        ("Associated Token Account", (name.to_string(), None))
+    } else if *program_id == compute_budget::id() {
+        let instruction = borsh::from_slice::<ComputeBudgetInstruction>(data).ok()?;
+        ("Compute Budget", split_debug(format!("{:?}", instruction)))
+    } else if *program_id == MEMO_V1_ID || *program_id == MEMO_V2_ID {
+        let memo = std::str::from_utf8(data).ok()?;
+        ("Memo", ("Memo".to_string(), Some(format!("{:?}", memo))))
+    } else if *program_id == stake::program::id() {
+        let instruction = bincode::deserialize::<StakeInstruction>(data).ok()?;
+        ("Stake Program", split_debug(format!("{:?}", instruction)))
+    } else if *program_id == vote::program::id() {
+        let instruction = bincode::deserialize::<VoteInstruction>(data).ok()?;
+        ("Vote Program", split_debug(format!("{:?}", instruction)))
+    } else {
+        return None;
+    };
+    let (program, (name, args)) = decoded;
+    Some((program, name, args))
+}
+
+/// decode an instruction of the well known programs, anything else is kept raw
+pub fn decode_instruction<A: ToString>(
+    program_id: &Pubkey,
+    accounts: &[A],
+    data: &[u8],
+) -> DecodedInstruction {
+    let Some((program, name, args)) = decode_known(program_id, data) else {
+        return DecodedInstruction {
+            program: program_id.to_string(),
+            name: "Unknown".to_string(),
+            args: None,
+            accounts: accounts
+                .iter()
+                .enumerate()
+                .map(|(i, account)| (format!("account #{}", i), account.to_string()))
+                .collect(),
+            raw_data: Some(data.iter().map(|byte| format!("{:02x}", byte)).collect()),
+        };
+    };
+
+    let labels = account_labels(program_id, &name);
+    DecodedInstruction {
+        program: program.to_string(),
+        accounts: accounts
+            .iter()
+            .enumerate()
+            .map(|(i, account)| {
+                let label = labels
+                    .get(i)
+                    .map(|label| label.to_string())
+                    .unwrap_or_else(|| format!("account #{}", i));
+                (label, account.to_string())
+            })
+            .collect(),
+        name,
+        args,
+        raw_data: None,
+    }
+}
+
+/// print a decoded instruction, `label` is its position like `#1` or `#1.2`
+pub fn print_instruction(label: &str, instruction: &DecodedInstruction, indent: usize) {
+    let pad = " ".repeat(indent);
+    println!(
+        "{}{} {} {}",
+        pad,
+        style(label).bold(),
+        style(&instruction.program).cyan(),
+        style(&instruction.name).green().bold()
+    );
+    if let Some(args) = &instruction.args {
+        println!("{}    {}", pad, style(args).yellow());
+    }
+    for (label, account) in &instruction.accounts {
+        println!(
+            "{}    {} {}",
+            pad,
+            style(format!("{}:", label)).dim(),
+            account
+        );
+    }
+    if let Some(raw_data) = &instruction.raw_data {
+        println!(
+            "{}    {} {}",
+            pad,
+            style("data:").dim(),
+            style(if raw_data.is_empty() {
+                "<empty>"
+            } else {
+                raw_data
+            })
+            .magenta()
+        );
+    }
+}
+
+#[test]
+fn test_decode_instruction() {
+    let from = Pubkey::new_unique();
+    let to = Pubkey::new_unique();
+    let transfer = solana_sdk::system_instruction::transfer(&from, &to, 42);
+    let decoded = decode_instruction(&transfer.program_id, &[from, to], &transfer.data);
+    assert_eq!(decoded.program, "System Program");
+    assert_eq!(decoded.name, "Transfer");
+    assert_eq!(decoded.args.as_deref(), Some("{ lamports: 42 }"));
+    assert_eq!(decoded.accounts[1], ("to".to_string(), to.to_string()));
+
+    let price = ComputeBudgetInstruction::set_compute_unit_price(1_000);
+    let decoded = decode_instruction::<Pubkey>(&price.program_id, &[], &price.data);
+    assert_eq!(decoded.name, "SetComputeUnitPrice");
+
+    let unknown = decode_instruction(&Pubkey::new_unique(), &[from], &[1, 2]);
+    assert_eq!(unknown.raw_data.as_deref(), Some("0102"));
+}
@@ -0,0 +1,549 @@
+use crate::config::{get_rpc_client, get_state_dir, read_solana_config};
+use crate::tx_manage::history::fetch_parsed_transaction;
+use crate::utils::default_account;
+use crate::wallet_manage::account_create_timestamp::fetch_signatures;
+use chrono::{DateTime, Utc};
+use clap::{Parser, ValueEnum};
+use console::style;
+use futures::StreamExt;
+use serde::Serialize;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+use solana_transaction_status_client_types::{
+    EncodedTransaction, EncodedTransactionWithStatusMeta, UiMessage,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// export an accounting ledger of every balance change of a wallet
+#[derive(Parser, Debug)]
+pub struct ExportArgs {
+    /// Wallet address (optional, uses the default wallet if not provided)
+    pub address: Option<String>,
+
+    /// Ledger format
+    #[clap(long, value_enum, default_value_t = ExportFormat::Csv)]
+    pub format: ExportFormat,
+
+    /// File the entries are appended to, stdout if omitted
+    #[clap(short, long)]
+    pub output: Option<String>,
+
+    /// Ignore the saved cursor and export the full history
+    #[clap(long)]
+    pub full: bool,
+
+    /// Number of transactions fetched at the same time
+    #[clap(long, default_value = "8")]
+    pub concurrency: usize,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Beancount,
+    Ledger,
+}
+
+/// A balance change of one asset within a transaction
+#[derive(Debug)]
+struct Leg {
+    /// "SOL" or the mint address
+    asset: String,
+    change: i128,
+    decimals: u8,
+    counterparty: Option<String>,
+}
+
+/// Everything a transaction changed for the exported wallet
+#[derive(Debug)]
+struct LedgerEntry {
+    signature: String,
+    slot: u64,
+    block_time: Option<i64>,
+    failed: bool,
+    /// lamports paid as fee, only when the wallet was the fee payer
+    fee: Option<u64>,
+    legs: Vec<Leg>,
+}
+
+#[derive(Debug, Serialize)]
+struct CsvRow<'a> {
+    date: String,
+    signature: &'a str,
+    slot: u64,
+    kind: &'static str,
+    asset: &'a str,
+    amount: String,
+    counterparty: Option<&'a str>,
+    tag: Option<&'a str>,
+}
+
+/// a signed amount without the plus sign
+fn format_amount(change: i128, decimals: u8) -> String {
+    let amount = u64::try_from(change.unsigned_abs()).unwrap_or(u64::MAX);
+    let amount = spl_token::amount_to_ui_amount_string_trimmed(amount, decimals);
+    if change < 0 {
+        format!("-{}", amount)
+    } else {
+        amount
+    }
+}
+
+fn format_date(block_time: Option<i64>, format: &str) -> String {
+    block_time
+        .and_then(|time| DateTime::<Utc>::from_timestamp(time, 0))
+        .map(|time| time.format(format).to_string())
+        .unwrap_or_else(|| "1970-01-01".to_string())
+}
+
+/// the account whose change mirrors ours the most, the other side of the transfer
+fn counterparty<'a>(changes: impl Iterator<Item = (&'a str, i128)>, ours: i128) -> Option<String> {
+    changes
+        .filter(|(_, change)| change.signum() == -ours.signum() && *change != 0)
+        .max_by_key(|(_, change)| change.unsigned_abs())
+        .map(|(address, _)| address.to_string())
+}
+
+fn build_entry(
+    status: &RpcConfirmedTransactionStatusWithSignature,
+    transaction: &EncodedTransactionWithStatusMeta,
+    address: &str,
+) -> LedgerEntry {
+    let mut entry = LedgerEntry {
+        signature: status.signature.clone(),
+        slot: status.slot,
+        block_time: status.block_time,
+        failed: status.err.is_some(),
+        fee: None,
+        legs: vec![],
+    };
+
+    let (EncodedTransaction::Json(ui_transaction), Some(meta)) =
+        (&transaction.transaction, &transaction.meta)
+    else {
+        return entry;
+    };
+    let UiMessage::Parsed(message) = &ui_transaction.message else {
+        return entry;
+    };
+    let keys = message
+        .account_keys
+        .iter()
+        .map(|account| account.pubkey.as_str())
+        .collect::<Vec<_>>();
+
+    let sol_changes = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| {
+            let pre = meta.pre_balances.get(i).copied().unwrap_or_default() as i128;
+            let post = meta.post_balances.get(i).copied().unwrap_or_default() as i128;
+            (*key, post - pre)
+        })
+        .collect::<Vec<_>>();
+    if let Some(index) = keys.iter().position(|key| *key == address) {
+        let mut change = sol_changes[index].1;
+        // the fee is booked on its own, so it is taken out of the transfer amount
+        if index == 0 {
+            entry.fee = Some(meta.fee);
+            change += meta.fee as i128;
+        }
+        if change != 0 {
+            entry.legs.push(Leg {
+                asset: "SOL".to_string(),
+                change,
+                decimals: 9,
+                counterparty: counterparty(
+                    sol_changes
+                        .iter()
+                        .filter(|(key, _)| *key != address)
+                        .map(|(key, change)| (*key, *change)),
+                    change,
+                ),
+            });
+        }
+    }
+
+    // token changes per (mint, owner)
+    let mut token_changes: BTreeMap<(String, String), (i128, u8)> = BTreeMap::new();
+    for (balances, sign) in [
+        (&meta.pre_token_balances, -1i128),
+        (&meta.post_token_balances, 1),
+    ] {
+        let OptionSerializer::Some(balances) = balances else {
+            continue;
+        };
+        for balance in balances {
+            let owner = match &balance.owner {
+                OptionSerializer::Some(owner) => owner.clone(),
+                _ => continue,
+            };
+            let amount = balance.ui_token_amount.amount.parse::<i128>().unwrap_or(0);
+            let change = token_changes
+                .entry((balance.mint.clone(), owner))
+                .or_insert((0, balance.ui_token_amount.decimals));
+            change.0 += sign * amount;
+        }
+    }
+    for ((mint, owner), (change, decimals)) in &token_changes {
+        if owner != address || *change == 0 {
+            continue;
+        }
+        let others = token_changes
+            .iter()
+            .filter(|((other_mint, other_owner), _)| other_mint == mint && other_owner != address)
+            .map(|((_, other_owner), (other_change, _))| (other_owner.as_str(), *other_change));
+        entry.legs.push(Leg {
+            asset: mint.clone(),
+            change: *change,
+            decimals: *decimals,
+            counterparty: counterparty(others, *change),
+        });
+    }
+
+    entry
+}
+
+/// commodity names may only hold upper case letters, digits and a few marks
+fn commodity(asset: &str, labels: &HashMap<String, String>) -> String {
+    if asset == "SOL" {
+        return asset.to_string();
+    }
+    let name = labels
+        .get(asset)
+        .cloned()
+        .unwrap_or_else(|| format!("M{}", &asset[..8.min(asset.len())]));
+    let name = name
+        .to_uppercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(20)
+        .collect::<String>();
+    match name.chars().next() {
+        Some(first) if first.is_ascii_alphabetic() => name,
+        _ => format!("M{}", name),
+    }
+}
+
+fn write_csv(
+    out: &mut dyn Write,
+    entries: &[LedgerEntry],
+    labels: &HashMap<String, String>,
+    with_header: bool,
+) -> anyhow::Result<()> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(with_header)
+        .from_writer(out);
+    for entry in entries {
+        let date = format_date(entry.block_time, "%Y-%m-%d %H:%M:%S");
+        if let Some(fee) = entry.fee {
+            writer.serialize(CsvRow {
+                date: date.clone(),
+                signature: &entry.signature,
+                slot: entry.slot,
+                kind: "fee",
+                asset: "SOL",
+                amount: format_amount(-(fee as i128), 9),
+                counterparty: None,
+                tag: None,
+            })?;
+        }
+        for leg in &entry.legs {
+            let counterparty = leg.counterparty.as_deref();
+            writer.serialize(CsvRow {
+                date: date.clone(),
+                signature: &entry.signature,
+                slot: entry.slot,
+                kind: if leg.change < 0 { "out" } else { "in" },
+                asset: &leg.asset,
+                amount: format_amount(leg.change, leg.decimals),
+                counterparty,
+                tag: counterparty
+                    .and_then(|counterparty| labels.get(counterparty))
+                    .map(String::as_str),
+            })?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+const WALLET_ACCOUNT: &str = "Assets:Solana:Wallet";
+const FEES_ACCOUNT: &str = "Expenses:Solana:Fees";
+
+/// the income or expense account balancing a leg
+fn other_account(leg: &Leg) -> &'static str {
+    if leg.change < 0 {
+        "Expenses:Solana:Transfers"
+    } else {
+        "Income:Solana:Transfers"
+    }
+}
+
+/// the accounts a beancount file already opens, so appending does not open them twice
+fn read_opened_accounts(path: &str) -> HashSet<String> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(
+            |line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [_, "open", account, ..] => Some(account.to_string()),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+/// beancount rejects postings to accounts without an `open` directive, so every
+/// account not in `opened` is opened at the date of the first entry
+fn write_open_directives(
+    out: &mut dyn Write,
+    entries: &[&LedgerEntry],
+    opened: &HashSet<String>,
+) -> anyhow::Result<()> {
+    let Some(first) = entries.first() else {
+        return Ok(());
+    };
+    let mut accounts = vec![];
+    for entry in entries {
+        if entry.fee.is_some() {
+            accounts.extend([FEES_ACCOUNT, WALLET_ACCOUNT]);
+        }
+        for leg in &entry.legs {
+            accounts.extend([WALLET_ACCOUNT, other_account(leg)]);
+        }
+    }
+    let mut seen = HashSet::new();
+    let accounts = accounts
+        .into_iter()
+        .filter(|account| !opened.contains(*account) && seen.insert(*account))
+        .collect::<Vec<_>>();
+    if accounts.is_empty() {
+        return Ok(());
+    }
+
+    let date = format_date(first.block_time, "%Y-%m-%d");
+    for account in accounts {
+        writeln!(out, "{} open {}", date, account)?;
+    }
+    writeln!(out)?;
+    Ok(())
+}
+
+/// beancount and ledger share the layout, they differ in date and metadata syntax
+fn write_journal(
+    out: &mut dyn Write,
+    entries: &[LedgerEntry],
+    labels: &HashMap<String, String>,
+    format: ExportFormat,
+    opened: &HashSet<String>,
+) -> anyhow::Result<()> {
+    let entries = entries
+        .iter()
+        .filter(|entry| entry.fee.is_some() || !entry.legs.is_empty())
+        .collect::<Vec<_>>();
+    if format == ExportFormat::Beancount {
+        write_open_directives(out, &entries, opened)?;
+    }
+
+    for entry in entries {
+        let payee = entry
+            .legs
+            .iter()
+            .find_map(|leg| leg.counterparty.as_deref())
+            .map(|counterparty| {
+                labels
+                    .get(counterparty)
+                    .cloned()
+                    .unwrap_or_else(|| counterparty.to_string())
+            })
+            .unwrap_or_else(|| "Solana".to_string());
+        let tags = entry
+            .legs
+            .iter()
+            .filter_map(|leg| leg.counterparty.as_ref())
+            .filter_map(|counterparty| labels.get(counterparty))
+            .map(|label| label.replace(' ', "-"))
+            .collect::<Vec<_>>();
+        let flag = if entry.failed { "!" } else { "*" };
+
+        match format {
+            ExportFormat::Beancount => {
+                let tags = tags
+                    .iter()
+                    .map(|tag| format!(" #{}", tag))
+                    .collect::<String>();
+                writeln!(
+                    out,
+                    "{} {} {:?} {:?}{}",
+                    format_date(entry.block_time, "%Y-%m-%d"),
+                    flag,
+                    payee,
+                    entry.signature,
+                    tags
+                )?;
+                writeln!(out, "  signature: {:?}", entry.signature)?;
+            }
+            _ => {
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    format_date(entry.block_time, "%Y/%m/%d"),
+                    flag,
+                    payee
+                )?;
+                writeln!(out, "    ; signature: {}", entry.signature)?;
+                if !tags.is_empty() {
+                    writeln!(out, "    ; :{}:", tags.join(":"))?;
+                }
+            }
+        }
+
+        // every leg is balanced by its own income or expense posting
+        let indent = if format == ExportFormat::Beancount {
+            "  "
+        } else {
+            "    "
+        };
+        let quote = |commodity: String| {
+            if format == ExportFormat::Ledger {
+                format!("\"{}\"", commodity)
+            } else {
+                commodity
+            }
+        };
+        if let Some(fee) = entry.fee {
+            let fee = format_amount(fee as i128, 9);
+            writeln!(out, "{}{}  {} SOL", indent, FEES_ACCOUNT, fee)?;
+            writeln!(out, "{}{}  -{} SOL", indent, WALLET_ACCOUNT, fee)?;
+        }
+        for leg in &entry.legs {
+            let commodity = quote(commodity(&leg.asset, labels));
+            writeln!(
+                out,
+                "{}{}  {} {}",
+                indent,
+                WALLET_ACCOUNT,
+                format_amount(leg.change, leg.decimals),
+                commodity
+            )?;
+            writeln!(
+                out,
+                "{}{}  {} {}",
+                indent,
+                other_account(leg),
+                format_amount(-leg.change, leg.decimals),
+                commodity
+            )?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn cursor_path(address: &Pubkey, format: ExportFormat) -> anyhow::Result<PathBuf> {
+    let format = format!("{:?}", format).to_lowercase();
+    Ok(get_state_dir()?.join(format!("export-{}-{}.cursor", address, format)))
+}
+
+pub async fn handle_export(args: &ExportArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let address: Pubkey = match &args.address {
+        Some(address) => address.parse()?,
+        None => default_account()?.pubkey(),
+    };
+    let labels = read_solana_config()?.address_labels().clone();
+
+    // the cursor is the newest signature of the previous export
+    let cursor_path = cursor_path(&address, args.format)?;
+    let cursor = if args.full {
+        None
+    } else {
+        std::fs::read_to_string(&cursor_path)
+            .ok()
+            .map(|cursor| cursor.trim().parse::<Signature>())
+            .transpose()?
+    };
+
+    let mut signatures = vec![];
+    let mut before = None;
+    loop {
+        let page = fetch_signatures(&client, &address, before, cursor, Some(1000)).await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(last.signature.parse()?);
+        let full_page = page.len() == 1000;
+        signatures.extend(page);
+        if !full_page {
+            break;
+        }
+    }
+    // the ledger runs oldest first
+    signatures.reverse();
+
+    eprintln!(
+        "{} {} {}",
+        style("Exporting").bold().cyan(),
+        style(signatures.len()).yellow(),
+        style("new transactions").cyan()
+    );
+
+    let wallet = address.to_string();
+    let entries = futures::stream::iter(signatures.iter().map(|status| {
+        let client = &client;
+        let wallet = &wallet;
+        async move {
+            let signature: Signature = status.signature.parse()?;
+            let transaction = fetch_parsed_transaction(client, &signature).await?;
+            anyhow::Ok(build_entry(status, &transaction, wallet))
+        }
+    }))
+    .buffered(args.concurrency.max(1))
+    .collect::<Vec<_>>()
+    .await
+    .into_iter()
+    .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // read before the file is opened for appending
+    let opened = match (&args.output, args.format) {
+        (Some(path), ExportFormat::Beancount) => read_opened_accounts(path),
+        _ => HashSet::new(),
+    };
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?,
+        ),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    match args.format {
+        ExportFormat::Csv => {
+            // appending to an existing file must not repeat the header
+            let with_header = args.output.as_ref().is_none_or(|path| {
+                std::fs::metadata(path).map_or(true, |metadata| metadata.len() == 0)
+            });
+            write_csv(&mut out, &entries, &labels, with_header)?;
+        }
+        format => write_journal(&mut out, &entries, &labels, format, &opened)?,
+    }
+    out.flush()?;
+
+    if let Some(newest) = signatures.last() {
+        std::fs::write(&cursor_path, &newest.signature)?;
+    }
+    eprintln!(
+        "{} {}",
+        style("Cursor saved to").dim(),
+        style(cursor_path.display()).dim()
+    );
+
+    Ok(())
+}
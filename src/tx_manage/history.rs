@@ -0,0 +1,413 @@
+use crate::config::get_rpc_client;
+use crate::output::{print_csv, print_json, OutputFormat};
+use crate::utils::default_account;
+use crate::wallet_manage::account_create_timestamp::fetch_signatures;
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::Parser;
+use console::style;
+use futures::StreamExt;
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+use solana_transaction_status_client_types::{
+    EncodedTransaction, EncodedTransactionWithStatusMeta, UiInstruction, UiMessage,
+    UiParsedInstruction, UiTransactionEncoding, UiTransactionTokenBalance,
+};
+use std::collections::BTreeMap;
+
+/// list the transactions of an address
+#[derive(Parser, Debug)]
+pub struct HistoryArgs {
+    /// Account address (optional, uses the default wallet if not provided)
+    pub address: Option<String>,
+
+    /// Maximum number of transactions to list
+    #[clap(short, long, default_value = "100")]
+    pub limit: usize,
+
+    /// Start searching backwards from this signature
+    #[clap(long)]
+    pub before: Option<String>,
+
+    /// Stop once this signature is reached
+    #[clap(long)]
+    pub until: Option<String>,
+
+    /// Only transactions on or after this date (YYYY-MM-DD, UTC)
+    #[clap(long)]
+    pub from: Option<String>,
+
+    /// Only transactions on or before this date (YYYY-MM-DD, UTC)
+    #[clap(long)]
+    pub to: Option<String>,
+
+    /// Only list failed transactions
+    #[clap(long)]
+    pub failed_only: bool,
+
+    /// Fetch every transaction to show balance changes and instructions
+    #[clap(short, long)]
+    pub details: bool,
+
+    /// Number of transactions fetched at the same time with --details
+    #[clap(long, default_value = "8")]
+    pub concurrency: usize,
+
+    /// Output format
+    #[clap(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryRow {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub date: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub memo: Option<String>,
+    pub sol_change: Option<String>,
+    pub token_changes: Option<String>,
+    pub instructions: Option<String>,
+}
+
+/// The parts of a fetched transaction relevant to one address
+#[derive(Debug, Default)]
+pub struct TransactionDetails {
+    /// lamport change of the address, fee included when it paid
+    pub sol_change: i128,
+    /// raw amount change per mint with its decimals
+    pub token_changes: BTreeMap<String, (i128, u8)>,
+    /// `program: type` of every top-level instruction
+    pub instructions: Vec<String>,
+}
+
+fn parse_date(date: &str) -> anyhow::Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date {}, expected YYYY-MM-DD", date))
+}
+
+fn format_block_time(block_time: Option<i64>) -> String {
+    block_time
+        .and_then(|time| DateTime::<Utc>::from_timestamp(time, 0))
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+/// format a signed raw amount with the given decimals
+pub fn format_change(change: i128, decimals: u8) -> String {
+    let sign = if change < 0 { "-" } else { "+" };
+    let amount = u64::try_from(change.unsigned_abs()).unwrap_or(u64::MAX);
+    format!(
+        "{}{}",
+        sign,
+        spl_token::amount_to_ui_amount_string_trimmed(amount, decimals)
+    )
+}
+
+/// the signatures matching the filters, newest first
+async fn collect_signatures(
+    client: &RpcClient,
+    address: &Pubkey,
+    args: &HistoryArgs,
+) -> anyhow::Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+    let from = args
+        .from
+        .as_deref()
+        .map(parse_date)
+        .transpose()?
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc().timestamp());
+    let to = args
+        .to
+        .as_deref()
+        .map(parse_date)
+        .transpose()?
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .map(|time| time.and_utc().timestamp());
+    let until = args.until.as_deref().map(str::parse).transpose()?;
+
+    let mut before: Option<Signature> = args.before.as_deref().map(str::parse).transpose()?;
+    let mut signatures = vec![];
+    'pages: loop {
+        let page = fetch_signatures(client, address, before, until, Some(1000)).await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(last.signature.parse()?);
+        let full_page = page.len() == 1000;
+
+        for status in page {
+            if let (Some(from), Some(block_time)) = (from, status.block_time) {
+                // pages are newest first, nothing older can match
+                if block_time < from {
+                    break 'pages;
+                }
+            }
+            if to.is_some_and(|to| status.block_time.is_some_and(|time| time > to)) {
+                continue;
+            }
+            if args.failed_only && status.err.is_none() {
+                continue;
+            }
+            signatures.push(status);
+            if signatures.len() >= args.limit {
+                break 'pages;
+            }
+        }
+
+        if !full_page {
+            break;
+        }
+    }
+
+    Ok(signatures)
+}
+
+/// fetch a transaction as parsed json, which also resolves lookup table accounts
+pub async fn fetch_parsed_transaction(
+    client: &RpcClient,
+    signature: &Signature,
+) -> anyhow::Result<EncodedTransactionWithStatusMeta> {
+    Ok(client
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::JsonParsed),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?
+        .transaction)
+}
+
+fn token_balances_of(
+    balances: Option<&Vec<UiTransactionTokenBalance>>,
+    address: &str,
+) -> BTreeMap<String, (i128, u8)> {
+    let mut amounts = BTreeMap::new();
+    for balance in balances.into_iter().flatten() {
+        if balance.owner.as_ref().map(String::as_str) != Some(address) {
+            continue;
+        }
+        let amount = balance.ui_token_amount.amount.parse::<i128>().unwrap_or(0);
+        let entry = amounts
+            .entry(balance.mint.clone())
+            .or_insert((0, balance.ui_token_amount.decimals));
+        entry.0 += amount;
+    }
+    amounts
+}
+
+/// the balance changes and instructions of a transaction seen from `address`
+pub fn transaction_details(
+    transaction: &EncodedTransactionWithStatusMeta,
+    address: &Pubkey,
+) -> TransactionDetails {
+    let mut details = TransactionDetails::default();
+    let address = address.to_string();
+
+    let EncodedTransaction::Json(ui_transaction) = &transaction.transaction else {
+        return details;
+    };
+    let UiMessage::Parsed(message) = &ui_transaction.message else {
+        return details;
+    };
+
+    for instruction in &message.instructions {
+        details.instructions.push(match instruction {
+            UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) => {
+                match parsed.parsed.get("type").and_then(|kind| kind.as_str()) {
+                    Some(kind) => format!("{}: {}", parsed.program, kind),
+                    None => parsed.program.clone(),
+                }
+            }
+            UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(partial)) => {
+                partial.program_id.clone()
+            }
+            UiInstruction::Compiled(compiled) => message
+                .account_keys
+                .get(compiled.program_id_index as usize)
+                .map(|account| account.pubkey.clone())
+                .unwrap_or_default(),
+        });
+    }
+
+    let Some(meta) = &transaction.meta else {
+        return details;
+    };
+    if let Some(index) = message
+        .account_keys
+        .iter()
+        .position(|account| account.pubkey == address)
+    {
+        let pre = meta.pre_balances.get(index).copied().unwrap_or_default();
+        let post = meta.post_balances.get(index).copied().unwrap_or_default();
+        details.sol_change = post as i128 - pre as i128;
+    }
+
+    let pre = token_balances_of(meta.pre_token_balances.as_ref().into(), &address);
+    let post = token_balances_of(meta.post_token_balances.as_ref().into(), &address);
+    for mint in pre.keys().chain(post.keys()) {
+        let (before, decimals) = pre.get(mint).copied().unwrap_or((0, 0));
+        let (after, post_decimals) = post.get(mint).copied().unwrap_or((0, decimals));
+        if after != before {
+            details
+                .token_changes
+                .insert(mint.clone(), (after - before, post_decimals.max(decimals)));
+        }
+    }
+
+    details
+}
+
+pub async fn handle_history(args: &HistoryArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let address: Pubkey = match &args.address {
+        Some(address) => address.parse()?,
+        None => default_account()?.pubkey(),
+    };
+
+    let signatures = collect_signatures(&client, &address, args).await?;
+
+    // buffered keeps the newest-first order while fetching in parallel
+    let details = if args.details {
+        futures::stream::iter(signatures.iter().map(|status| {
+            let client = &client;
+            async move {
+                let transaction = match status.signature.parse::<Signature>() {
+                    Ok(signature) => fetch_parsed_transaction(client, &signature).await,
+                    Err(e) => Err(e.into()),
+                };
+                // the row stays in the history, only its details are left empty
+                match transaction {
+                    Ok(transaction) => Some(transaction_details(&transaction, &address)),
+                    Err(e) => {
+                        eprintln!(
+                            "{} {}",
+                            style("WARNING:").yellow().bold(),
+                            style(format!("Failed to fetch {}: {}", status.signature, e)).yellow()
+                        );
+                        None
+                    }
+                }
+            }
+        }))
+        .buffered(args.concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await
+    } else {
+        signatures.iter().map(|_| None).collect()
+    };
+
+    let rows = signatures
+        .into_iter()
+        .zip(details)
+        .map(|(status, details)| HistoryRow {
+            date: format_block_time(status.block_time),
+            status: if status.err.is_some() {
+                "failed".to_string()
+            } else {
+                "success".to_string()
+            },
+            error: status.err.map(|err| err.to_string()),
+            sol_change: details
+                .as_ref()
+                .map(|details| format_change(details.sol_change, 9)),
+            token_changes: details.as_ref().map(|details| {
+                details
+                    .token_changes
+                    .iter()
+                    .map(|(mint, (change, decimals))| {
+                        format!("{} {}", format_change(*change, *decimals), mint)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            }),
+            instructions: details.map(|details| details.instructions.join("; ")),
+            signature: status.signature,
+            slot: status.slot,
+            block_time: status.block_time,
+            memo: status.memo,
+        })
+        .collect::<Vec<_>>();
+
+    match args.output {
+        OutputFormat::Json => print_json(&rows),
+        OutputFormat::Csv => print_csv(rows),
+        OutputFormat::Table => {
+            print_history_table(&address, &rows);
+            Ok(())
+        }
+    }
+}
+
+fn print_history_table(address: &Pubkey, rows: &[HistoryRow]) {
+    println!(
+        "\n{} {}",
+        style("📜 Transaction history of").bold().underlined(),
+        style(address).yellow()
+    );
+
+    if rows.is_empty() {
+        println!("{}", style("No transactions found").dim().italic());
+        println!();
+        return;
+    }
+
+    for row in rows {
+        let status = if row.error.is_some() {
+            style("✘").red()
+        } else {
+            style("✔").green()
+        };
+        println!(
+            "{} {} {}",
+            status,
+            style(&row.date).dim(),
+            style(&row.signature).cyan()
+        );
+        if let Some(error) = &row.error {
+            println!("    {} {}", style("error:").red(), error);
+        }
+        if let Some(sol_change) = &row.sol_change {
+            println!(
+                "    {} {}",
+                style("SOL:").cyan(),
+                style(sol_change).yellow()
+            );
+        }
+        if let Some(token_changes) = row.token_changes.as_ref().filter(|c| !c.is_empty()) {
+            println!(
+                "    {} {}",
+                style("tokens:").cyan(),
+                style(token_changes).yellow()
+            );
+        }
+        if let Some(instructions) = &row.instructions {
+            println!("    {} {}", style("instructions:").cyan(), instructions);
+        }
+        if let Some(memo) = &row.memo {
+            println!("    {} {}", style("memo:").cyan(), memo);
+        }
+    }
+    println!(
+        "\n{} {}\n",
+        style("Transactions:").bold().cyan(),
+        style(rows.len()).yellow()
+    );
+}
+
+#[test]
+fn test_format_change() {
+    assert_eq!(format_change(1_500_000_000, 9), "+1.5");
+    assert_eq!(format_change(-5_000, 9), "-0.000005");
+    assert_eq!(format_change(0, 6), "+0");
+}
@@ -0,0 +1,276 @@
+use crate::tx_manage::decode::{decode_instruction, message_accounts, print_instruction};
+use base64::Engine;
+use clap::Parser;
+use console::style;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::signature::Signature;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::VersionedTransaction;
+use std::io::Read;
+
+/// decode a serialized transaction without any rpc access
+#[derive(Parser, Debug)]
+pub struct InspectArgs {
+    /// base58 or base64 encoded transaction, read from stdin if omitted
+    #[clap(conflicts_with = "file")]
+    pub transaction: Option<String>,
+
+    /// File holding the transaction, encoded or raw bytes
+    #[clap(short, long)]
+    pub file: Option<String>,
+}
+
+/// read the raw input from the argument, the file or stdin
+pub fn read_input(transaction: Option<&str>, file: Option<&str>) -> anyhow::Result<Vec<u8>> {
+    Ok(match (transaction, file) {
+        (Some(transaction), _) => transaction.as_bytes().to_vec(),
+        (None, Some(file)) => std::fs::read(file)?,
+        (None, None) => {
+            let mut input = vec![];
+            std::io::stdin().read_to_end(&mut input)?;
+            input
+        }
+    })
+}
+
+/// deserialize the whole input as `T`, trailing bytes are rejected
+fn deserialize_exact<T: serde::de::DeserializeOwned + serde::Serialize>(bytes: &[u8]) -> Option<T> {
+    let value = bincode::deserialize::<T>(bytes).ok()?;
+    (bincode::serialized_size(&value).ok()? == bytes.len() as u64).then_some(value)
+}
+
+/// a signed or partially signed transaction, a bare message is accepted too
+fn parse_bytes(bytes: &[u8]) -> Option<VersionedTransaction> {
+    if let Some(transaction) = deserialize_exact::<VersionedTransaction>(bytes) {
+        return Some(transaction);
+    }
+    let message = deserialize_exact::<VersionedMessage>(bytes)?;
+    let signatures = vec![Signature::default(); message.header().num_required_signatures as usize];
+    Some(VersionedTransaction {
+        signatures,
+        message,
+    })
+}
+
+/// parse base64, base58 or raw bytes, whichever decodes into a transaction
+pub fn parse_transaction(input: &[u8]) -> anyhow::Result<VersionedTransaction> {
+    let mut candidates = vec![];
+    if let Ok(text) = std::str::from_utf8(input) {
+        let text = text.trim();
+        if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(text) {
+            candidates.push(bytes);
+        }
+        if let Ok(bytes) = bs58::decode(text).into_vec() {
+            candidates.push(bytes);
+        }
+    }
+    candidates.push(input.to_vec());
+
+    candidates
+        .iter()
+        .find_map(|bytes| parse_bytes(bytes))
+        .ok_or_else(|| anyhow::anyhow!("Input is neither a transaction nor a message"))
+}
+
+pub fn handle_inspect(args: &InspectArgs) -> anyhow::Result<()> {
+    let input = read_input(args.transaction.as_deref(), args.file.as_deref())?;
+    let transaction = parse_transaction(&input)?;
+    print_transaction(&transaction);
+    Ok(())
+}
+
+pub fn print_transaction(transaction: &VersionedTransaction) {
+    let message = &transaction.message;
+    let accounts = message_accounts(message, None);
+    let header = message.header();
+
+    println!("\n{}", style("🔍 Transaction").bold().underlined());
+    println!(
+        "{} {}",
+        style("Version:").bold().cyan(),
+        style(match message {
+            VersionedMessage::Legacy(_) => "legacy",
+            VersionedMessage::V0(_) => "v0",
+        })
+        .yellow()
+    );
+    println!(
+        "{} {}",
+        style("Message hash:").bold().cyan(),
+        style(message.hash()).yellow()
+    );
+    println!(
+        "{} {} signed ({} readonly), {} readonly unsigned",
+        style("Header:").bold().cyan(),
+        header.num_required_signatures,
+        header.num_readonly_signed_accounts,
+        header.num_readonly_unsigned_accounts
+    );
+
+    // a durable nonce transaction advances the nonce in its first instruction
+    let nonce = message.instructions().first().and_then(|instruction| {
+        let program_id = accounts.get(instruction.program_id_index as usize)?.pubkey;
+        if program_id != system_program::id() {
+            return None;
+        }
+        match bincode::deserialize::<SystemInstruction>(&instruction.data).ok()? {
+            SystemInstruction::AdvanceNonceAccount => {
+                let account = |position: usize| {
+                    let index = *instruction.accounts.get(position)? as usize;
+                    accounts.get(index).map(|account| account.pubkey)
+                };
+                Some((account(0)?, account(2)?))
+            }
+            _ => None,
+        }
+    });
+    match nonce {
+        Some((nonce_account, authority)) => {
+            println!(
+                "{} {}",
+                style("Durable nonce:").bold().cyan(),
+                style(message.recent_blockhash()).yellow()
+            );
+            println!(
+                "  {} {}",
+                style("nonce account:").dim(),
+                style(nonce_account).yellow()
+            );
+            println!(
+                "  {} {}",
+                style("nonce authority:").dim(),
+                style(authority).yellow()
+            );
+        }
+        None => println!(
+            "{} {}",
+            style("Recent blockhash:").bold().cyan(),
+            style(message.recent_blockhash()).yellow()
+        ),
+    }
+
+    println!("\n{}", style("Signers:").bold().cyan());
+    let verified = transaction.verify_with_results();
+    for (i, signer) in accounts
+        .iter()
+        .take(header.num_required_signatures as usize)
+        .enumerate()
+    {
+        let signature = transaction.signatures.get(i);
+        let state = match signature {
+            None => style("missing".to_string()).red(),
+            Some(signature) if *signature == Signature::default() => {
+                style("missing".to_string()).red()
+            }
+            Some(_) if verified.get(i).copied().unwrap_or(false) => {
+                style("valid".to_string()).green()
+            }
+            Some(_) => style("INVALID".to_string()).red().bold(),
+        };
+        println!(
+            "  {:>3} {} {}{}",
+            style(format!("#{}", i)).dim(),
+            style(signer.pubkey).yellow(),
+            state,
+            if i == 0 { " (fee payer)" } else { "" }
+        );
+    }
+
+    println!("\n{}", style("Accounts:").bold().cyan());
+    for (i, account) in accounts.iter().enumerate() {
+        let mut roles = vec![];
+        if account.signer {
+            roles.push("signer");
+        }
+        if account.writable {
+            roles.push("writable");
+        }
+        println!(
+            "  {:>3} {} {}",
+            style(format!("#{}", i)).dim(),
+            style(account.pubkey).yellow(),
+            style(format!("[{}]", roles.join(", "))).dim()
+        );
+    }
+
+    // lookup table accounts can not be resolved offline, name them by table and index
+    let mut keys = accounts
+        .iter()
+        .map(|account| account.pubkey.to_string())
+        .collect::<Vec<_>>();
+    if let Some(lookups) = message
+        .address_table_lookups()
+        .filter(|lookups| !lookups.is_empty())
+    {
+        println!("\n{}", style("Address lookup tables:").bold().cyan());
+        for lookup in lookups {
+            println!(
+                "  {} writable {:?} readonly {:?}",
+                style(lookup.account_key).yellow(),
+                lookup.writable_indexes,
+                lookup.readonly_indexes
+            );
+        }
+        for lookup in lookups {
+            for index in &lookup.writable_indexes {
+                keys.push(format!("{}[{}] (writable)", lookup.account_key, index));
+            }
+        }
+        for lookup in lookups {
+            for index in &lookup.readonly_indexes {
+                keys.push(format!("{}[{}]", lookup.account_key, index));
+            }
+        }
+    }
+
+    println!("\n{}", style("Instructions:").bold().cyan());
+    for (i, instruction) in message.instructions().iter().enumerate() {
+        let program_id = accounts
+            .get(instruction.program_id_index as usize)
+            .map(|account| account.pubkey)
+            .unwrap_or_default();
+        let instruction_accounts = instruction
+            .accounts
+            .iter()
+            .map(|index| {
+                keys.get(*index as usize)
+                    .cloned()
+                    .unwrap_or_else(|| format!("<invalid index {}>", index))
+            })
+            .collect::<Vec<_>>();
+        let decoded = decode_instruction(&program_id, &instruction_accounts, &instruction.data);
+        print_instruction(&format!("#{}", i + 1), &decoded, 2);
+    }
+    println!();
+}
+
+#[test]
+fn test_parse_transaction() {
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::Transaction;
+
+    let payer = Keypair::new();
+    let transfer = solana_sdk::system_instruction::transfer(
+        &payer.pubkey(),
+        &solana_sdk::pubkey::Pubkey::new_unique(),
+        1,
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[transfer],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::default(),
+    );
+    let bytes = bincode::serialize(&transaction).unwrap();
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+    let parsed = parse_transaction(encoded.as_bytes()).unwrap();
+    assert_eq!(parsed.verify_with_results(), vec![true]);
+
+    let message = bincode::serialize(&transaction.message).unwrap();
+    let encoded = bs58::encode(message).into_string();
+    let parsed = parse_transaction(encoded.as_bytes()).unwrap();
+    assert_eq!(parsed.signatures, vec![Signature::default()]);
+}
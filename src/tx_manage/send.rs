@@ -0,0 +1,197 @@
+use crate::config::get_rpc_client;
+use crate::tx_manage::inspect::{parse_transaction, read_input};
+use crate::tx_manage::status::{
+    report_statuses, wait_for_statuses, Commitment, StatusExit, EXIT_OK,
+};
+use crate::utils::read_keypair_or_default;
+use clap::Parser;
+use console::style;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::SerializableTransaction;
+use solana_client::rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+use std::time::Duration;
+
+/// broadcast a serialized transaction and track it to confirmation
+#[derive(Parser, Debug)]
+pub struct SendArgs {
+    /// base58 or base64 encoded transaction, read from stdin if omitted
+    #[clap(conflicts_with = "file")]
+    pub transaction: Option<String>,
+
+    /// File holding the transaction, encoded or raw bytes
+    #[clap(short, long)]
+    pub file: Option<String>,
+
+    /// Add our signature where the transaction still needs it
+    #[clap(short, long)]
+    pub sign: bool,
+
+    /// Keypair path used with --sign (optional, uses default if not provided)
+    #[clap(short, long, requires = "sign")]
+    pub keypair: Option<String>,
+
+    /// Simulate the transaction and stop if the simulation fails
+    #[clap(long)]
+    pub simulate: bool,
+
+    /// Skip the preflight check of the rpc node
+    #[clap(long)]
+    pub skip_preflight: bool,
+
+    /// Commitment to wait for
+    #[clap(short, long, value_enum, default_value_t = Commitment::Confirmed)]
+    pub commitment: Commitment,
+
+    /// Seconds to wait for the confirmation
+    #[clap(short, long, default_value = "60")]
+    pub timeout: u64,
+}
+
+/// sign the slot of `signer` if it is a required signer, returns whether it was
+fn add_signature(transaction: &mut VersionedTransaction, signer: &dyn Signer) -> bool {
+    let required = transaction.message.header().num_required_signatures as usize;
+    let Some(position) = transaction
+        .message
+        .static_account_keys()
+        .get(..required)
+        .and_then(|keys| keys.iter().position(|key| *key == signer.pubkey()))
+    else {
+        return false;
+    };
+    let signature = signer.sign_message(&transaction.message.serialize());
+    match transaction.signatures.get_mut(position) {
+        Some(slot) => *slot = signature,
+        None => return false,
+    }
+    true
+}
+
+/// simulate the transaction and print its logs, fails if the simulation does
+pub async fn simulate(
+    client: &RpcClient,
+    transaction: &impl SerializableTransaction,
+    config: RpcSimulateTransactionConfig,
+) -> anyhow::Result<()> {
+    let config = RpcSimulateTransactionConfig {
+        commitment: config.commitment.or(Some(client.commitment())),
+        ..config
+    };
+    let simulation = client
+        .simulate_transaction_with_config(transaction, config)
+        .await?
+        .value;
+    println!("\n{}", style("Simulation:").bold().cyan());
+    for log in simulation.logs.unwrap_or_default() {
+        println!("  {}", style(log).dim());
+    }
+    if let Some(units) = simulation.units_consumed {
+        println!(
+            "{} {}",
+            style("Compute units:").bold().cyan(),
+            style(units).yellow()
+        );
+    }
+    if let Some(err) = simulation.err {
+        anyhow::bail!("Simulation failed: {}", err);
+    }
+    println!("{}", style("Simulation succeeded").green());
+    Ok(())
+}
+
+/// send the transaction, then wait for `commitment` and report it like
+/// `tx status`, failing with its exit code when it did not succeed
+pub async fn broadcast(
+    client: &RpcClient,
+    transaction: &VersionedTransaction,
+    skip_preflight: bool,
+    commitment: Commitment,
+    timeout: u64,
+) -> anyhow::Result<()> {
+    let signature = client
+        .send_transaction_with_config(
+            transaction,
+            RpcSendTransactionConfig {
+                skip_preflight,
+                ..Default::default()
+            },
+        )
+        .await?;
+    println!(
+        "\n{}: {}",
+        style("Transaction signature").cyan(),
+        style(signature).yellow()
+    );
+
+    let commitment = CommitmentConfig::from(commitment);
+    let statuses = wait_for_statuses(
+        client,
+        &[signature],
+        commitment,
+        Duration::from_secs(timeout),
+    )
+    .await?;
+    let exit_code = report_statuses(client, &[signature], &statuses, commitment).await;
+    if exit_code != EXIT_OK {
+        return Err(StatusExit(exit_code).into());
+    }
+
+    Ok(())
+}
+
+pub async fn handle_send(args: &SendArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let input = read_input(args.transaction.as_deref(), args.file.as_deref())?;
+    let mut transaction = parse_transaction(&input)?;
+    let required = transaction.message.header().num_required_signatures as usize;
+    transaction
+        .signatures
+        .resize(required, Signature::default());
+    // the input is untrusted, the header and keys have to agree before indexing
+    transaction
+        .sanitize()
+        .map_err(|e| anyhow::anyhow!("Malformed transaction: {}", e))?;
+
+    if args.sign {
+        let keypair = read_keypair_or_default(args.keypair.as_deref())?;
+        if !add_signature(&mut transaction, &keypair) {
+            anyhow::bail!("{} is not a signer of this transaction", keypair.pubkey());
+        }
+        println!(
+            "{} {}",
+            style("Signed as").bold().cyan(),
+            style(keypair.pubkey()).yellow()
+        );
+    }
+
+    // refuse to broadcast with missing or broken signatures
+    let verified = transaction.verify_with_results();
+    let signers = transaction.message.static_account_keys();
+    let unsigned = verified
+        .iter()
+        .zip(&transaction.signatures)
+        .zip(signers)
+        .filter(|((valid, signature), _)| !**valid || **signature == Signature::default())
+        .map(|(_, signer)| signer.to_string())
+        .collect::<Vec<_>>();
+    if !unsigned.is_empty() {
+        anyhow::bail!("Missing or invalid signatures for: {}", unsigned.join(", "));
+    }
+
+    if args.simulate {
+        simulate(&client, &transaction, Default::default()).await?;
+    }
+
+    broadcast(
+        &client,
+        &transaction,
+        args.skip_preflight,
+        args.commitment,
+        args.timeout,
+    )
+    .await?;
+
+    Ok(())
+}
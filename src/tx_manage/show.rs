@@ -0,0 +1,266 @@
+use crate::config::get_rpc_client;
+use crate::tx_manage::decode::{decode_instruction, message_accounts, print_instruction};
+use crate::tx_manage::history::format_change;
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use console::style;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+use solana_transaction_status_client_types::{UiInstruction, UiTransactionEncoding};
+use std::collections::BTreeMap;
+
+/// decode and print a confirmed transaction
+#[derive(Parser, Debug)]
+pub struct ShowArgs {
+    /// Transaction signature
+    pub signature: String,
+
+    /// Hide the program logs
+    #[clap(long)]
+    pub no_logs: bool,
+}
+
+fn parse_pubkeys(addresses: &[String]) -> anyhow::Result<Vec<Pubkey>> {
+    Ok(addresses
+        .iter()
+        .map(|address| address.parse())
+        .collect::<Result<_, _>>()?)
+}
+
+pub async fn handle_show(args: &ShowArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let signature: Signature = args.signature.parse()?;
+
+    let confirmed = client
+        .get_transaction_with_config(
+            &signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+    let transaction = confirmed
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| anyhow::anyhow!("Unable to decode transaction {}", signature))?;
+    let meta = confirmed
+        .transaction
+        .meta
+        .ok_or_else(|| anyhow::anyhow!("Transaction {} has no status meta", signature))?;
+
+    let loaded = match &meta.loaded_addresses {
+        OptionSerializer::Some(loaded) => Some((
+            parse_pubkeys(&loaded.writable)?,
+            parse_pubkeys(&loaded.readonly)?,
+        )),
+        _ => None,
+    };
+    let accounts = message_accounts(
+        &transaction.message,
+        loaded
+            .as_ref()
+            .map(|(writable, readonly)| (writable.as_slice(), readonly.as_slice())),
+    );
+    let keys = accounts
+        .iter()
+        .map(|account| account.pubkey)
+        .collect::<Vec<_>>();
+
+    println!(
+        "\n{} {}",
+        style("🧾 Transaction").bold().underlined(),
+        style(signature).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Slot:").bold().cyan(),
+        style(confirmed.slot).yellow()
+    );
+    if let Some(time) = confirmed
+        .block_time
+        .and_then(|time| DateTime::<Utc>::from_timestamp(time, 0))
+    {
+        println!(
+            "{} {} {}",
+            style("Block time:").bold().cyan(),
+            style(time.format("%Y-%m-%d %H:%M:%S")).green(),
+            style("UTC").dim()
+        );
+    }
+    match &meta.err {
+        Some(err) => println!(
+            "{} {}",
+            style("Status:").bold().cyan(),
+            style(format!("failed: {}", err)).red()
+        ),
+        None => println!(
+            "{} {}",
+            style("Status:").bold().cyan(),
+            style("success").green()
+        ),
+    }
+    println!(
+        "{} {} {}",
+        style("Fee:").bold().cyan(),
+        style(lamports_to_sol(meta.fee)).yellow(),
+        style("SOL").cyan()
+    );
+    if let OptionSerializer::Some(units) = &meta.compute_units_consumed {
+        println!(
+            "{} {}",
+            style("Compute units:").bold().cyan(),
+            style(units).yellow()
+        );
+    }
+
+    println!("\n{}", style("Accounts:").bold().cyan());
+    for (i, account) in accounts.iter().enumerate() {
+        let mut roles = vec![];
+        if i == 0 {
+            roles.push("fee payer");
+        }
+        if account.signer {
+            roles.push("signer");
+        }
+        if account.writable {
+            roles.push("writable");
+        }
+        let pre = meta.pre_balances.get(i).copied().unwrap_or_default();
+        let post = meta.post_balances.get(i).copied().unwrap_or_default();
+        let change = if post == pre {
+            style(String::new())
+        } else if post > pre {
+            style(format!(
+                "{} SOL",
+                format_change(post as i128 - pre as i128, 9)
+            ))
+            .green()
+        } else {
+            style(format!(
+                "{} SOL",
+                format_change(post as i128 - pre as i128, 9)
+            ))
+            .red()
+        };
+        println!(
+            "  {:>3} {} {} {}",
+            style(format!("#{}", i)).dim(),
+            style(account.pubkey).yellow(),
+            style(format!("[{}]", roles.join(", "))).dim(),
+            change
+        );
+        if let Some(table) = account.lookup_table {
+            println!("        {} {}", style("from lookup table").dim(), table);
+        }
+    }
+
+    let inner_instructions = match &meta.inner_instructions {
+        OptionSerializer::Some(inner) => inner
+            .iter()
+            .map(|inner| (inner.index, &inner.instructions))
+            .collect::<BTreeMap<_, _>>(),
+        _ => BTreeMap::new(),
+    };
+
+    println!("\n{}", style("Instructions:").bold().cyan());
+    for (i, instruction) in transaction.message.instructions().iter().enumerate() {
+        let program_id = keys
+            .get(instruction.program_id_index as usize)
+            .copied()
+            .unwrap_or_default();
+        let instruction_accounts = instruction
+            .accounts
+            .iter()
+            .filter_map(|index| keys.get(*index as usize).copied())
+            .collect::<Vec<_>>();
+        let decoded = decode_instruction(&program_id, &instruction_accounts, &instruction.data);
+        print_instruction(&format!("#{}", i + 1), &decoded, 2);
+
+        let Some(inner) = inner_instructions.get(&(i as u8)) else {
+            continue;
+        };
+        for (j, inner) in inner.iter().enumerate() {
+            let UiInstruction::Compiled(inner) = inner else {
+                continue;
+            };
+            let program_id = keys
+                .get(inner.program_id_index as usize)
+                .copied()
+                .unwrap_or_default();
+            let inner_accounts = inner
+                .accounts
+                .iter()
+                .filter_map(|index| keys.get(*index as usize).copied())
+                .collect::<Vec<_>>();
+            let data = bs58::decode(&inner.data).into_vec().unwrap_or_default();
+            let decoded = decode_instruction(&program_id, &inner_accounts, &data);
+            // nested cpi calls are indented by their stack height
+            let depth = inner.stack_height.unwrap_or(2).saturating_sub(1) as usize;
+            print_instruction(&format!("#{}.{}", i + 1, j + 1), &decoded, 2 + depth * 4);
+        }
+    }
+
+    let pre_tokens = match &meta.pre_token_balances {
+        OptionSerializer::Some(balances) => balances.as_slice(),
+        _ => &[],
+    };
+    let post_tokens = match &meta.post_token_balances {
+        OptionSerializer::Some(balances) => balances.as_slice(),
+        _ => &[],
+    };
+    let mut token_changes = BTreeMap::new();
+    for (balances, sign) in [(pre_tokens, -1i128), (post_tokens, 1)] {
+        for balance in balances {
+            let amount = balance.ui_token_amount.amount.parse::<i128>().unwrap_or(0);
+            let owner = match &balance.owner {
+                OptionSerializer::Some(owner) => owner.clone(),
+                _ => String::new(),
+            };
+            let entry = token_changes
+                .entry((balance.account_index, balance.mint.clone()))
+                .or_insert((owner, 0i128, balance.ui_token_amount.decimals));
+            entry.1 += sign * amount;
+        }
+    }
+    let token_changes = token_changes
+        .into_iter()
+        .filter(|(_, (_, change, _))| *change != 0)
+        .collect::<Vec<_>>();
+    if !token_changes.is_empty() {
+        println!("\n{}", style("Token balance changes:").bold().cyan());
+        for ((account_index, mint), (owner, change, decimals)) in token_changes {
+            let change = format_change(change, decimals);
+            println!(
+                "  {} {} {} {} {}",
+                style(format!("#{}", account_index)).dim(),
+                style(owner).yellow(),
+                if change.starts_with('-') {
+                    style(change).red()
+                } else {
+                    style(change).green()
+                },
+                style("of").dim(),
+                style(mint).cyan()
+            );
+        }
+    }
+
+    if !args.no_logs {
+        if let OptionSerializer::Some(logs) = &meta.log_messages {
+            println!("\n{}", style("Logs:").bold().cyan());
+            for log in logs {
+                println!("  {}", style(log).dim());
+            }
+        }
+    }
+    println!();
+
+    Ok(())
+}
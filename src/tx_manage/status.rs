@@ -0,0 +1,250 @@
+use crate::config::get_rpc_client;
+use clap::{Parser, ValueEnum};
+use console::style;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_request::MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::{TransactionStatus, UiTransactionEncoding};
+use std::time::{Duration, Instant};
+
+/// Exit code when every transaction reached the commitment without error
+pub const EXIT_OK: i32 = 0;
+/// Exit code when at least one transaction failed on chain
+pub const EXIT_FAILED: i32 = 2;
+/// Exit code when at least one transaction was not found or did not reach
+/// the commitment in time
+pub const EXIT_PENDING: i32 = 3;
+
+/// Returned by handlers whose summary is not `EXIT_OK`, `main` exits with the code
+#[derive(Debug)]
+pub struct StatusExit(pub i32);
+
+impl std::fmt::Display for StatusExit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "exit code {}", self.0)
+    }
+}
+
+impl std::error::Error for StatusExit {}
+
+/// query the status of one or more transactions
+///
+/// Exits with 0 when all transactions reached the commitment, 2 when one of
+/// them failed and 3 when one is unknown or still pending.
+#[derive(Parser, Debug)]
+pub struct StatusArgs {
+    /// Transaction signatures
+    #[clap(required = true)]
+    pub signatures: Vec<String>,
+
+    /// Block until every transaction reaches the commitment or fails
+    #[clap(short, long)]
+    pub wait: bool,
+
+    /// Commitment the transactions have to reach
+    #[clap(short, long, value_enum, default_value_t = Commitment::Confirmed)]
+    pub commitment: Commitment,
+
+    /// Seconds to wait before giving up
+    #[clap(short, long, default_value = "60")]
+    pub timeout: u64,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Commitment {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl From<Commitment> for CommitmentConfig {
+    fn from(commitment: Commitment) -> Self {
+        match commitment {
+            Commitment::Processed => CommitmentConfig::processed(),
+            Commitment::Confirmed => CommitmentConfig::confirmed(),
+            Commitment::Finalized => CommitmentConfig::finalized(),
+        }
+    }
+}
+
+/// whether the status is final for the given commitment, either reached or failed
+fn is_settled(status: &Option<TransactionStatus>, commitment: CommitmentConfig) -> bool {
+    status
+        .as_ref()
+        .is_some_and(|status| status.err.is_some() || status.satisfies_commitment(commitment))
+}
+
+/// the statuses of any number of signatures, the rpc takes 256 per request
+pub async fn fetch_statuses(
+    client: &RpcClient,
+    signatures: &[Signature],
+) -> anyhow::Result<Vec<Option<TransactionStatus>>> {
+    let mut statuses = Vec::with_capacity(signatures.len());
+    for chunk in signatures.chunks(MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS) {
+        statuses.extend(
+            client
+                .get_signature_statuses_with_history(chunk)
+                .await?
+                .value,
+        );
+    }
+    Ok(statuses)
+}
+
+/// poll the statuses until all of them settle or the timeout runs out
+pub async fn wait_for_statuses(
+    client: &RpcClient,
+    signatures: &[Signature],
+    commitment: CommitmentConfig,
+    timeout: Duration,
+) -> anyhow::Result<Vec<Option<TransactionStatus>>> {
+    let started = Instant::now();
+    loop {
+        let statuses = fetch_statuses(client, signatures).await?;
+        let settled = statuses.iter().all(|status| is_settled(status, commitment));
+        if settled || started.elapsed() >= timeout {
+            return Ok(statuses);
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+/// the fee paid, only available once the transaction is confirmed
+async fn fetch_fee(client: &RpcClient, signature: &Signature) -> Option<u64> {
+    let transaction = client
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await
+        .ok()?;
+    transaction.transaction.meta.map(|meta| meta.fee)
+}
+
+fn print_status(
+    signature: &Signature,
+    status: &Option<TransactionStatus>,
+    fee: Option<u64>,
+    commitment: CommitmentConfig,
+) {
+    println!(
+        "\n{} {}",
+        style("Signature:").bold().cyan(),
+        style(signature).yellow()
+    );
+
+    let Some(status) = status else {
+        println!(
+            "{} {}",
+            style("Status:").bold().cyan(),
+            style("not found").red()
+        );
+        return;
+    };
+
+    let state = match &status.err {
+        Some(_) => style("failed".to_string()).red(),
+        None if status.satisfies_commitment(commitment) => {
+            style(format!("{:?}", status.confirmation_status()).to_lowercase()).green()
+        }
+        None => {
+            style(format!("{:?} (pending)", status.confirmation_status()).to_lowercase()).yellow()
+        }
+    };
+    println!("{} {}", style("Status:").bold().cyan(), state);
+    println!(
+        "{} {}",
+        style("Slot:").bold().cyan(),
+        style(status.slot).yellow()
+    );
+    println!(
+        "{} {}",
+        style("Confirmations:").bold().cyan(),
+        style(
+            status
+                .confirmations
+                .map(|confirmations| confirmations.to_string())
+                .unwrap_or_else(|| "rooted".to_string())
+        )
+        .yellow()
+    );
+    if let Some(err) = &status.err {
+        println!(
+            "{} {}",
+            style("Error:").bold().cyan(),
+            style(err.to_string()).red()
+        );
+    }
+    if let Some(fee) = fee {
+        println!(
+            "{} {} {}",
+            style("Fee:").bold().cyan(),
+            style(lamports_to_sol(fee)).yellow(),
+            style("SOL").cyan()
+        );
+    }
+}
+
+pub async fn handle_status(args: &StatusArgs) -> anyhow::Result<()> {
+    let client = get_rpc_client()?;
+    let signatures = args
+        .signatures
+        .iter()
+        .map(|signature| signature.parse())
+        .collect::<Result<Vec<Signature>, _>>()?;
+    let commitment = CommitmentConfig::from(args.commitment);
+
+    let statuses = if args.wait {
+        wait_for_statuses(
+            &client,
+            &signatures,
+            commitment,
+            Duration::from_secs(args.timeout),
+        )
+        .await?
+    } else {
+        fetch_statuses(&client, &signatures).await?
+    };
+
+    let exit_code = report_statuses(&client, &signatures, &statuses, commitment).await;
+    if exit_code != EXIT_OK {
+        return Err(StatusExit(exit_code).into());
+    }
+    Ok(())
+}
+
+/// print every status and return the exit code summarizing them
+pub async fn report_statuses(
+    client: &RpcClient,
+    signatures: &[Signature],
+    statuses: &[Option<TransactionStatus>],
+    commitment: CommitmentConfig,
+) -> i32 {
+    let mut exit_code = EXIT_OK;
+    for (signature, status) in signatures.iter().zip(statuses) {
+        let fee = match status {
+            Some(_) => fetch_fee(client, signature).await,
+            None => None,
+        };
+        print_status(signature, status, fee, commitment);
+
+        let code = match status {
+            Some(status) if status.err.is_some() => EXIT_FAILED,
+            Some(status) if status.satisfies_commitment(commitment) => EXIT_OK,
+            _ => EXIT_PENDING,
+        };
+        // a failure outranks a pending transaction
+        if code == EXIT_FAILED || exit_code == EXIT_OK {
+            exit_code = code;
+        }
+    }
+    println!();
+    exit_code
+}
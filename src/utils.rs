@@ -1,17 +1,114 @@
-use solana_sdk::signature::Keypair;
+use console::style;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
 
 /// default accout at ~/.config/solana/id.json
 pub fn default_account() -> anyhow::Result<Keypair> {
     // default address at ~/.config/solana/id.json
     // 构造保存路径
     let home_dir = dirs::home_dir().expect("Could not find home directory");
-    let keypair_path = home_dir
-        .join(".config")
-        .join("solana")
-        .join(format!("id.json"));
+    let keypair_path = home_dir.join(".config").join("solana").join("id.json");
 
     // read keypair from file
-    let keypair = solana_sdk::signature::read_keypair_file(&keypair_path.to_str().unwrap())
+    let keypair = solana_sdk::signature::read_keypair_file(keypair_path.to_str().unwrap())
         .map_err(|e| anyhow::anyhow!("{}", e.to_string()))?;
     Ok(keypair)
 }
+
+/// read keypair from the given path, falling back to the default account
+pub fn read_keypair_or_default(path: Option<&str>) -> anyhow::Result<Keypair> {
+    match path {
+        Some(path) => solana_sdk::signature::read_keypair_file(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read keypair file {}: {}", path, e)),
+        None => default_account(),
+    }
+}
+
+/// ask the user to type `expected` before an irreversible action
+pub fn confirm_typed(prompt: &str, expected: &str) -> anyhow::Result<bool> {
+    let term = console::Term::stdout();
+    term.write_line(&format!(
+        "{} Type {} to continue:",
+        console::style(prompt).red().bold(),
+        console::style(expected).yellow()
+    ))?;
+    let input = term.read_line()?;
+    Ok(input.trim() == expected)
+}
+
+/// ask a yes/no question, anything but `y` or `yes` declines
+pub fn confirm(prompt: &str) -> anyhow::Result<bool> {
+    let term = console::Term::stdout();
+    term.write_str(&format!("{} [y/N] ", console::style(prompt).bold()))?;
+    let input = term.read_line()?;
+    Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// send one batch of close instructions paid by `wallet` and report it,
+/// returning the lamports reclaimed or 0 when the batch failed
+pub async fn send_close_batch(
+    client: &RpcClient,
+    wallet: &Keypair,
+    instructions: &[Instruction],
+    closed: &str,
+    lamports: u64,
+) -> anyhow::Result<u64> {
+    let recent_blockhash = client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&wallet.pubkey()),
+        &[wallet],
+        recent_blockhash,
+    );
+
+    match client.send_and_confirm_transaction(&transaction).await {
+        Ok(signature) => {
+            println!(
+                "{} Closed {}, reclaimed {} SOL: {}",
+                style("✔").green(),
+                closed,
+                style(lamports_to_sol(lamports)).green(),
+                style(signature).cyan()
+            );
+            Ok(lamports)
+        }
+        Err(e) => {
+            println!(
+                "{} {}",
+                style("ERROR:").red().bold(),
+                style(format!("Failed to close batch: {:?}", e)).red()
+            );
+            Ok(0)
+        }
+    }
+}
+
+/// the footer of the commands that close accounts
+pub fn print_total_recovered(lamports: u64) {
+    println!(
+        "\n{} {} {}",
+        style("Total recovered:").bold().cyan(),
+        style(lamports_to_sol(lamports)).green().bold(),
+        style("SOL").cyan()
+    );
+}
+
+/// decode a hex string, with or without a `0x` prefix
+pub fn decode_hex(hex: &str) -> anyhow::Result<Vec<u8>> {
+    let digits = hex.trim_start_matches("0x");
+    if !digits.len().is_multiple_of(2) {
+        anyhow::bail!("Invalid hex {}, odd number of digits", hex);
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            digits
+                .get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| anyhow::anyhow!("Invalid hex {}", hex))
+        })
+        .collect()
+}
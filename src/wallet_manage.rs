@@ -29,7 +29,7 @@ pub enum WalletMange {
         /// Sender wallet address, if it is not provided, the default wallet will be used
         #[clap(short, long)]
         from: Option<String>,
-        #[clap(short, long)]
+        #[clap(long)]
         to: String,
         #[clap(short, long)]
         amount: f64,
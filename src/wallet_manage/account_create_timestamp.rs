@@ -1,132 +1,342 @@
-use crate::config::get_rpc_client;
+use crate::config::{get_rpc_client, get_state_dir};
+use crate::tx_manage::history::fetch_parsed_transaction;
 use crate::utils::default_account;
 use chrono::prelude::*;
 use clap::Parser;
 use console::style;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::native_token::lamports_to_sol;
 use solana_sdk::{pubkey::Pubkey, signature::Signature, signer::Signer};
-use std::{
-    str::FromStr,
-    time::{Duration, UNIX_EPOCH},
+use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+use solana_transaction_status_client_types::{
+    EncodedTransaction, UiInstruction, UiMessage, UiParsedInstruction,
 };
+use std::path::PathBuf;
+
+/// largest page `getSignaturesForAddress` returns
+const PAGE_LIMIT: usize = 1000;
 
 #[derive(Parser, Debug)]
 pub struct AccountCreateTimestampArgs {
-    /// Account address
+    /// Account address, can be repeated (optional, uses default if not provided)
     #[clap(short, long)]
-    pub address: Option<String>,
+    pub address: Vec<String>,
+
+    /// Ignore the cached history and walk it again
+    #[clap(long)]
+    pub refresh: bool,
+
+    /// Number of addresses looked up at the same time
+    #[clap(long, default_value = "4")]
+    pub concurrency: usize,
+}
+
+/// What is known about the signature history of an account, cached between runs
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AccountAge {
+    pub newest_signature: Option<String>,
+    pub newest_block_time: Option<i64>,
+    pub oldest_signature: Option<String>,
+    pub oldest_block_time: Option<i64>,
+    pub transaction_count: u64,
+    /// set once the walk reached the first transaction
+    pub complete: bool,
+    pub funder: Option<String>,
+    pub funded_lamports: Option<u64>,
+}
+
+fn cache_path(addr: &Pubkey) -> anyhow::Result<PathBuf> {
+    let dir = get_state_dir()?.join("account-age");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir.join(format!("{}.json", addr)))
+}
+
+fn load_cache(addr: &Pubkey) -> Option<AccountAge> {
+    let content = std::fs::read_to_string(cache_path(addr).ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_cache(addr: &Pubkey, age: &AccountAge) -> anyhow::Result<()> {
+    std::fs::write(cache_path(addr)?, serde_json::to_string_pretty(age)?)?;
+    Ok(())
 }
 
 pub async fn handle_account_create_timestamp(
     args: &AccountCreateTimestampArgs,
 ) -> anyhow::Result<()> {
     let client = get_rpc_client()?;
-    if let Some(address) = args.address.as_ref() {
-        let addr: Pubkey = address.parse()?;
-        let datetime = get_account_creation_date(&client, &addr).await?;
-        let timestamp_str = datetime.format("%Y-%m-%d %H:%M:%S").to_string();
-
-        println!(
-            "\n{} {}",
-            style("Account:").bold().cyan(),
-            style(addr.to_string()).yellow()
-        );
+    let addresses = if args.address.is_empty() {
+        vec![default_account()?.pubkey()]
+    } else {
+        args.address
+            .iter()
+            .map(|address| address.parse())
+            .collect::<Result<Vec<Pubkey>, _>>()?
+    };
 
-        println!(
-            "{} {} {}\n",
-            style("Created on:").bold().cyan(),
-            style(timestamp_str).green(),
-            style("UTC").dim()
-        );
+    let results = futures::stream::iter(addresses.iter().map(|addr| {
+        let client = &client;
+        async move {
+            let cached = if args.refresh {
+                AccountAge::default()
+            } else {
+                load_cache(addr).unwrap_or_default()
+            };
+            let age = get_account_age(client, addr, cached, |age| save_cache(addr, age)).await?;
+            save_cache(addr, &age)?;
+            anyhow::Ok(age)
+        }
+    }))
+    .buffered(args.concurrency.max(1))
+    .collect::<Vec<_>>()
+    .await;
 
-        Ok(())
-    } else {
-        check_default_timestamp(&client).await
+    for (addr, result) in addresses.iter().zip(results) {
+        match result {
+            Ok(age) => print_account_age(addr, &age),
+            Err(e) => println!(
+                "\n{} {}",
+                style("ERROR:").red().bold(),
+                style(format!("Lookup of {} failed: {:?}", addr, e)).red()
+            ),
+        }
     }
-}
+    println!();
 
-async fn check_default_timestamp(client: &RpcClient) -> anyhow::Result<()> {
-    let keypair = default_account()?;
+    Ok(())
+}
 
-    let datetime = get_account_creation_date(&client, &keypair.pubkey()).await?;
-    let timestamp_str = datetime.format("%Y-%m-%d %H:%M:%S").to_string();
+fn format_block_time(block_time: Option<i64>) -> String {
+    block_time
+        .and_then(|time| DateTime::<Utc>::from_timestamp(time, 0))
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
 
+fn print_account_age(addr: &Pubkey, age: &AccountAge) {
     println!(
         "\n{} {}",
         style("Account:").bold().cyan(),
-        style(keypair.pubkey().to_string()).yellow()
+        style(addr.to_string()).yellow()
     );
 
+    if age.transaction_count == 0 {
+        println!("{}", style("No transactions found").dim().italic());
+        return;
+    }
+
     println!(
-        "{} {} {}\n",
+        "{} {} {}",
         style("Created on:").bold().cyan(),
-        style(timestamp_str).green(),
+        style(format_block_time(age.oldest_block_time)).green(),
         style("UTC").dim()
     );
-
-    Ok(())
+    if let Some(funder) = &age.funder {
+        println!(
+            "{} {} ({} SOL)",
+            style("First funder:").bold().cyan(),
+            style(funder).yellow(),
+            style(lamports_to_sol(age.funded_lamports.unwrap_or_default())).green()
+        );
+    }
+    println!(
+        "{} {} {}",
+        style("Last activity:").bold().cyan(),
+        style(format_block_time(age.newest_block_time)).green(),
+        style("UTC").dim()
+    );
+    println!(
+        "{} {}",
+        style("Transactions:").bold().cyan(),
+        style(age.transaction_count).yellow()
+    );
 }
 
-pub async fn get_account_creation_date(
+/// walk the signature history of the account, resuming from `age`. The
+/// walk is handed to `checkpoint` after every page of older history.
+pub async fn get_account_age(
     rpc: &RpcClient,
     addr: &Pubkey,
-) -> anyhow::Result<DateTime<Utc>> {
-    let mut before = None;
-    let mut earliest_sig: Option<RpcConfirmedTransactionStatusWithSignature> = None;
+    mut age: AccountAge,
+    checkpoint: impl Fn(&AccountAge) -> anyhow::Result<()>,
+) -> anyhow::Result<AccountAge> {
+    // signatures newer than the cached ones
+    if let Some(newest) = &age.newest_signature {
+        let until = Some(newest.parse::<Signature>()?);
+        let mut before = None;
+        let mut newest_seen = None;
+        loop {
+            let page = fetch_signatures(rpc, addr, before, until, Some(PAGE_LIMIT)).await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            newest_seen.get_or_insert_with(|| page[0].clone());
+            age.transaction_count += page.len() as u64;
+            before = Some(last.signature.parse()?);
+            if page.len() < PAGE_LIMIT {
+                break;
+            }
+        }
+        if let Some(newest) = newest_seen {
+            age.newest_signature = Some(newest.signature);
+            age.newest_block_time = newest.block_time;
+        }
+    }
 
-    loop {
-        let sigs = fetch_signatures(rpc, addr, before).await?;
+    // older history, pages come newest first so the last entry is the oldest
+    if !age.complete {
+        let mut before = age
+            .oldest_signature
+            .as_deref()
+            .map(str::parse::<Signature>)
+            .transpose()?;
+        loop {
+            let page = fetch_signatures(rpc, addr, before, None, Some(PAGE_LIMIT)).await?;
+            let Some(last) = page.last() else {
+                age.complete = true;
+                break;
+            };
+            if age.newest_signature.is_none() {
+                age.newest_signature = Some(page[0].signature.clone());
+                age.newest_block_time = page[0].block_time;
+            }
+            age.transaction_count += page.len() as u64;
+            age.oldest_signature = Some(last.signature.clone());
+            age.oldest_block_time = last.block_time;
+            before = Some(last.signature.parse()?);
 
-        if sigs.is_empty() {
-            break;
+            age.complete = page.len() < PAGE_LIMIT;
+            // the cursor is kept per page so an interrupted walk resumes here
+            checkpoint(&age)?;
+            if age.complete {
+                break;
+            }
         }
+    }
 
-        let mut sigs_vec = sigs;
-        sigs_vec.sort_by_key(|sig| sig.block_time);
-        let current_earliest = sigs_vec.first().unwrap().clone();
-
-        earliest_sig = Some(match earliest_sig {
-            None => current_earliest.clone(),
-            Some(prev) => {
-                if prev.block_time > current_earliest.block_time {
-                    current_earliest.clone()
-                } else {
-                    prev
+    if age.funder.is_none() {
+        if let Some(oldest) = &age.oldest_signature {
+            // non-archive nodes often can not return the oldest transaction
+            match find_funder(rpc, addr, &oldest.parse()?).await {
+                Ok(Some((funder, lamports))) => {
+                    age.funder = Some(funder);
+                    age.funded_lamports = Some(lamports);
                 }
+                Ok(None) => {}
+                Err(e) => eprintln!(
+                    "{} {}",
+                    style("WARNING:").yellow().bold(),
+                    style(format!("Funder lookup of {} failed: {}", addr, e)).yellow()
+                ),
             }
-        });
-
-        if sigs_vec.len() < 1000 {
-            break;
         }
-
-        before = Some(Signature::from_str(&current_earliest.signature)?);
     }
 
-    let status = earliest_sig.ok_or_else(|| anyhow::anyhow!("No signatures found!"))?;
+    Ok(age)
+}
+
+/// the creation date of an account, from its oldest transaction
+pub async fn get_account_creation_date(
+    rpc: &RpcClient,
+    addr: &Pubkey,
+) -> anyhow::Result<DateTime<Utc>> {
+    let cached = load_cache(addr).unwrap_or_default();
+    let age = get_account_age(rpc, addr, cached, |_| Ok(())).await?;
+    let block_time = age
+        .oldest_block_time
+        .ok_or_else(|| anyhow::anyhow!("No signatures found!"))?;
+    DateTime::<Utc>::from_timestamp(block_time, 0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid block time!"))
+}
 
-    let d = UNIX_EPOCH
-        + Duration::from_secs(
-            status
-                .block_time
-                .ok_or_else(|| anyhow::anyhow!("Missing block time!"))?
-                .try_into()?,
-        );
+/// who sent the first lamports to `addr`, from the system instructions of its
+/// earliest transaction, falling back to the fee payer
+async fn find_funder(
+    rpc: &RpcClient,
+    addr: &Pubkey,
+    signature: &Signature,
+) -> anyhow::Result<Option<(String, u64)>> {
+    let transaction = fetch_parsed_transaction(rpc, signature).await?;
+    let address = addr.to_string();
+
+    let EncodedTransaction::Json(ui_transaction) = &transaction.transaction else {
+        return Ok(None);
+    };
+    let UiMessage::Parsed(message) = &ui_transaction.message else {
+        return Ok(None);
+    };
+
+    let inner = match transaction
+        .meta
+        .as_ref()
+        .map(|meta| &meta.inner_instructions)
+    {
+        Some(OptionSerializer::Some(inner)) => inner
+            .iter()
+            .flat_map(|inner| inner.instructions.iter())
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+    for instruction in message.instructions.iter().chain(inner) {
+        let UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) = instruction else {
+            continue;
+        };
+        if parsed.program != "system" {
+            continue;
+        }
+        let info = &parsed.parsed["info"];
+        let (from, to) = match parsed.parsed["type"].as_str() {
+            Some("transfer") | Some("transferWithSeed") => (&info["source"], &info["destination"]),
+            Some("createAccount") | Some("createAccountWithSeed") => {
+                (&info["source"], &info["newAccount"])
+            }
+            _ => continue,
+        };
+        if to.as_str() == Some(address.as_str()) {
+            if let (Some(from), Some(lamports)) = (from.as_str(), info["lamports"].as_u64()) {
+                return Ok(Some((from.to_string(), lamports)));
+            }
+        }
+    }
 
-    Ok(DateTime::<Utc>::from(d))
+    // funded some other way, report the fee payer and the balance it left
+    let Some(meta) = &transaction.meta else {
+        return Ok(None);
+    };
+    let Some(index) = message
+        .account_keys
+        .iter()
+        .position(|account| account.pubkey == address)
+    else {
+        return Ok(None);
+    };
+    let pre = meta.pre_balances.get(index).copied().unwrap_or_default();
+    let post = meta.post_balances.get(index).copied().unwrap_or_default();
+    Ok(message
+        .account_keys
+        .first()
+        .filter(|payer| payer.pubkey != address)
+        .map(|payer| (payer.pubkey.clone(), post.saturating_sub(pre))))
 }
 
-async fn fetch_signatures(
+/// one page of signatures for the address, newest first, at most 1000
+pub async fn fetch_signatures(
     rpc: &RpcClient,
     addr: &Pubkey,
     before: Option<Signature>,
+    until: Option<Signature>,
+    limit: Option<usize>,
 ) -> anyhow::Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
     Ok(rpc
         .get_signatures_for_address_with_config(
             addr,
             solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config {
                 before,
+                until,
+                limit,
                 ..Default::default()
             },
         )
@@ -2,7 +2,7 @@ use crate::config::get_rpc_client;
 use crate::utils::default_account;
 use console::style;
 use solana_sdk::signer::Signer;
-use solana_sdk::{native_token::Sol, pubkey::Pubkey};
+use solana_sdk::{native_token::lamports_to_sol, pubkey::Pubkey};
 use std::str::FromStr;
 
 #[derive(Debug, clap::Parser)]
@@ -13,15 +13,15 @@ pub struct BalanceArgs {
 
 async fn check_balance(address: &str) -> anyhow::Result<()> {
     let client = get_rpc_client()?;
-    let pubkey = Pubkey::from_str(&address)?;
+    let pubkey = Pubkey::from_str(address)?;
     let balance = client.get_balance(&pubkey).await?;
-    let lamports = Sol(balance);
+    let sol = lamports_to_sol(balance);
 
     println!(
         "{} {}: {} {}",
         style("Balance for").cyan(),
         style(pubkey).yellow(),
-        style(lamports).green().bold(),
+        style(sol).green().bold(),
         style("SOL").cyan()
     );
     Ok(())
@@ -29,7 +29,7 @@ async fn check_balance(address: &str) -> anyhow::Result<()> {
 
 pub async fn display_balance(args: &BalanceArgs) -> anyhow::Result<()> {
     if let Some(address) = args.address.as_ref() {
-        check_balance(&address).await
+        check_balance(address).await
     } else {
         check_default_balance().await
     }
@@ -3,51 +3,72 @@ use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
 use std::fs;
 
-pub fn list_all_wallets() -> anyhow::Result<()> {
+/// every keypair file in ~/.config/solana, with its file name
+pub fn read_keystore() -> anyhow::Result<Vec<(String, Keypair)>> {
     let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Unable to get home directory"))?;
     let config_path = home.join(".config/solana");
 
-    println!("\n{}", style("🔑 Solana Wallets").bold().underlined());
+    let mut wallets = vec![];
+    for path in fs::read_dir(config_path)? {
+        let path = path?.path();
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        // 读取并解析密钥文件
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        // 移除可能的百分号和空白字符
+        let content = content.trim().trim_end_matches('%');
 
-    let paths = fs::read_dir(config_path)?;
-    let mut found = false;
+        // 尝试将字符串解析为字节数组
+        let Ok(bytes) = content
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(|s| s.trim().parse::<u8>())
+            .collect::<Result<Vec<u8>, _>>()
+        else {
+            continue;
+        };
 
-    for path in paths {
-        let path = path?.path();
-        if let Some(extension) = path.extension() {
-            if extension == "json" {
-                if let Some(file_name) = path.file_name() {
-                    // 读取并解析密钥文件
-                    if let Ok(content) = fs::read_to_string(&path) {
-                        // 移除可能的百分号和空白字符
-                        let content = content.trim().trim_end_matches('%');
-
-                        // 尝试将字符串解析为字节数组
-                        if let Ok(bytes) = content
-                            .trim_start_matches('[')
-                            .trim_end_matches(']')
-                            .split(',')
-                            .map(|s| s.trim().parse::<u8>())
-                            .collect::<Result<Vec<u8>, _>>()
-                        {
-                            found = true;
-
-                            // 从字节数组创建 Keypair
-                            if let Ok(keypair) = Keypair::from_bytes(&bytes) {
-                                println!(
-                                    "{} {}",
-                                    style(format!("→ {}", file_name.to_string_lossy())).cyan(),
-                                    style(keypair.pubkey().to_string()).yellow()
-                                );
-                            }
-                        }
-                    }
-                }
-            }
+        // 从字节数组创建 Keypair
+        if let Ok(keypair) = Keypair::from_bytes(&bytes) {
+            wallets.push((file_name.to_string_lossy().to_string(), keypair));
         }
     }
 
-    if !found {
+    wallets.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(wallets)
+}
+
+/// the keystore keypairs without duplicates, the same key is often saved
+/// under several file names
+pub fn unique_keystore_wallets() -> anyhow::Result<Vec<Keypair>> {
+    let mut seen = std::collections::HashSet::new();
+    Ok(read_keystore()?
+        .into_iter()
+        .map(|(_, keypair)| keypair)
+        .filter(|keypair| seen.insert(keypair.pubkey()))
+        .collect())
+}
+
+pub fn list_all_wallets() -> anyhow::Result<()> {
+    println!("\n{}", style("🔑 Solana Wallets").bold().underlined());
+
+    let wallets = read_keystore()?;
+    for (file_name, keypair) in &wallets {
+        println!(
+            "{} {}",
+            style(format!("→ {}", file_name)).cyan(),
+            style(keypair.pubkey().to_string()).yellow()
+        );
+    }
+
+    if wallets.is_empty() {
         println!("{}", style("No wallet config files found").dim().italic());
     }
 